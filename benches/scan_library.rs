@@ -0,0 +1,65 @@
+//! Times listing a library with a lot of unrelated entries in its `steamapps` directory
+//!
+//! Run with `cargo bench --bench scan_library`. This uses a plain timing loop instead of a
+//! benchmarking framework to keep the dev-dependencies light
+
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use steamlocate::Library;
+
+const NUM_APPS: u32 = 500;
+const NUM_OTHER_ENTRIES: u32 = 50_000;
+const ITERATIONS: u32 = 20;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let library_dir = env::temp_dir().join(format!("steamlocate-bench-{}", std::process::id()));
+    let steamapps = library_dir.join("steamapps");
+    fs::create_dir_all(steamapps.join("common"))?;
+    for app_id in 0..NUM_APPS {
+        fs::write(steamapps.join(format!("appmanifest_{app_id}.acf")), "")?;
+    }
+    // Stand-ins for everything else that piles up in there like workshop content and temp files
+    for i in 0..NUM_OTHER_ENTRIES {
+        fs::write(steamapps.join(format!("other_{i}.tmp")), "")?;
+    }
+
+    let result = (|| {
+        bench("Library::from_dir", &library_dir, Library::from_dir)?;
+        bench(
+            "Library::from_dir_shallow",
+            &library_dir,
+            Library::from_dir_shallow,
+        )
+    })();
+    fs::remove_dir_all(&library_dir)?;
+    result
+}
+
+fn bench(
+    name: &str,
+    library_dir: &Path,
+    from_dir: fn(&Path) -> steamlocate::Result<Library>,
+) -> Result<(), Box<dyn Error>> {
+    let mut times = Vec::new();
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let library = from_dir(library_dir)?;
+        times.push(start.elapsed());
+        assert_eq!(library.app_ids().len(), NUM_APPS as usize);
+    }
+
+    times.sort_unstable();
+    let median = times[times.len() / 2];
+    let total: Duration = times.iter().sum();
+    println!(
+        "{name}: median {median:?}, mean {:?} over {ITERATIONS} runs",
+        total / ITERATIONS,
+    );
+    Ok(())
+}
@@ -11,11 +11,9 @@ fn main() {
                 println!("    Library - {:?}", library.path());
                 for app in library.apps() {
                     match app {
-                        Ok(app) => println!(
-                            "        App {} - {}",
-                            app.app_id,
-                            app.name.as_deref().unwrap_or("<no-name>")
-                        ),
+                        Ok(app) => {
+                            println!("        App {} - {}", app.app_id, app.display_name())
+                        }
                         Err(err) => println!("        Failed reading app: {err}"),
                     }
                 }
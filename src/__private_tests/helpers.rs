@@ -1,8 +1,11 @@
 //! Some test helpers for setting up isolated dummy steam installations.
+//!
+//! These also back the public `testing` module (behind the `testing` feature), so there's only one
+//! place that knows how to lay out a fake installation
 
 use std::{
     collections::BTreeMap,
-    fs, iter,
+    fs, io,
     path::{Path, PathBuf},
 };
 
@@ -15,22 +18,23 @@ pub fn expect_test_env() -> TempSteamDir {
     TempSteamDir::builder()
         .app(SampleApp::GarrysMod.into())
         .app(SampleApp::Warframe.into())
-        .library(SampleApp::GraveyardKeeper.try_into().unwrap())
+        .library(SampleApp::GraveyardKeeper.into())
         .finish()
         .unwrap()
 }
 
 // TODO(cosmic): Add in functionality for providing shortcuts too
+#[derive(Debug)]
 pub struct TempSteamDir {
     steam_dir: crate::SteamDir,
-    _tmps: Vec<TempDir>,
+    _tmp: Option<TempDir>,
 }
 
 impl TryFrom<AppFile> for TempSteamDir {
     type Error = TestError;
 
     fn try_from(app: AppFile) -> Result<Self, Self::Error> {
-        Self::builder().app(app).finish()
+        Ok(Self::builder().app(app).finish()?)
     }
 }
 
@@ -46,7 +50,15 @@ impl TryFrom<SampleShortcuts> for TempSteamDir {
     type Error = TestError;
 
     fn try_from(sample_shortcuts: SampleShortcuts) -> Result<Self, Self::Error> {
-        Self::builder().shortcuts(sample_shortcuts).finish()
+        Ok(Self::builder().shortcuts(sample_shortcuts).finish()?)
+    }
+}
+
+impl TryFrom<SampleConfig> for TempSteamDir {
+    type Error = TestError;
+
+    fn try_from(sample_config: SampleConfig) -> Result<Self, Self::Error> {
+        Ok(Self::builder().config(sample_config).finish()?)
     }
 }
 
@@ -63,14 +75,43 @@ impl TempSteamDir {
 #[derive(Default)]
 #[must_use]
 pub struct TempSteamDirBuilder {
-    shortcuts: Option<SampleShortcuts>,
+    config: Option<String>,
+    nested_config: bool,
+    shortcuts: Option<Vec<u8>>,
+    user_id: Option<u32>,
     libraries: Vec<TempLibrary>,
     apps: Vec<AppFile>,
 }
 
 impl TempSteamDirBuilder {
-    pub fn shortcuts(mut self, shortcuts: SampleShortcuts) -> Self {
-        self.shortcuts = Some(shortcuts);
+    pub fn config(self, config: SampleConfig) -> Self {
+        self.config_contents(config.data())
+    }
+
+    /// Like [`Self::config()`], but places it at `steam/config/config.vdf` like some Linux
+    /// installs do
+    pub fn nested_config(mut self, config: SampleConfig) -> Self {
+        self.nested_config = true;
+        self.config(config)
+    }
+
+    pub fn config_contents(mut self, contents: impl Into<String>) -> Self {
+        self.config = Some(contents.into());
+        self
+    }
+
+    pub fn shortcuts(self, shortcuts: SampleShortcuts) -> Self {
+        self.shortcuts_contents(shortcuts.data())
+    }
+
+    pub fn shortcuts_contents(mut self, contents: impl Into<Vec<u8>>) -> Self {
+        self.shortcuts = Some(contents.into());
+        self
+    }
+
+    /// The account ID for the `userdata/<USER_ID>` directory. Defaults to `123123123`
+    pub fn user_id(mut self, user_id: u32) -> Self {
+        self.user_id = Some(user_id);
         self
     }
 
@@ -84,48 +125,74 @@ impl TempSteamDirBuilder {
         self
     }
 
+    /// Lays out the installation in a new temporary directory that's removed on drop
+    pub fn finish(self) -> io::Result<TempSteamDir> {
+        let tmp = TempDir::new()?;
+        let mut temp_steam_dir = self.finish_in(tmp.path())?;
+        temp_steam_dir._tmp = Some(tmp);
+        Ok(temp_steam_dir)
+    }
+
+    /// Lays out the installation at `dir/Steam` with each library at `dir/library-<N>`
     // Steam dir is also a library, but is laid out slightly differently than a regular library
-    pub fn finish(self) -> Result<TempSteamDir, TestError> {
+    pub fn finish_in(self, dir: &Path) -> io::Result<TempSteamDir> {
         let Self {
+            config,
+            nested_config,
             shortcuts,
+            user_id,
             libraries,
             apps,
         } = self;
 
-        let tmp = TempDir::new()?;
-        let root_dir = tmp.path().join("test-steam-dir");
-        let steam_dir = root_dir.join("Steam");
+        let steam_dir = dir.join("Steam");
         let apps_dir = steam_dir.join("steamapps");
         fs::create_dir_all(&apps_dir)?;
-        let shortcuts_dir = steam_dir.join("userdata").join("123123123").join("config");
-        fs::create_dir_all(&shortcuts_dir)?;
+        let user_config_dir = steam_dir
+            .join("userdata")
+            .join(user_id.unwrap_or(123_123_123).to_string())
+            .join("config");
+        fs::create_dir_all(&user_config_dir)?;
+
+        if let Some(config) = config {
+            let config_dir = if nested_config {
+                steam_dir.join("steam").join("config")
+            } else {
+                steam_dir.join("config")
+            };
+            fs::create_dir_all(&config_dir)?;
+            fs::write(config_dir.join("config.vdf"), config)?;
+        }
 
         if let Some(shortcuts) = shortcuts {
-            let data = shortcuts.data();
-            let shortcuts_file = shortcuts_dir.join("shortcuts.vdf");
-            fs::write(&shortcuts_file, data)?;
+            fs::write(user_config_dir.join("shortcuts.vdf"), shortcuts)?;
         }
 
         setup_steamapps_dir(&apps_dir, &apps)?;
 
         let steam_dir_content_id = i32::MIN;
-        let apps = apps.iter().map(|app| (app.id, 0)).collect();
-        let root_library =
-            LibraryFolder::mostly_default(steam_dir.clone(), steam_dir_content_id, apps);
-        setup_libraryfolders_file(&apps_dir, root_library, &libraries)?;
-
-        let tmps = iter::once(tmp)
-            .chain(libraries.into_iter().map(|library| library._tmp))
-            .collect();
+        let mut library_folders = vec![LibraryFolder::mostly_default(
+            steam_dir.clone(),
+            steam_dir_content_id,
+            &apps,
+        )];
+        for (i, library) in (1..).zip(&libraries) {
+            let path = dir.join(format!("library-{i}"));
+            library.write(&path)?;
+            library_folders.push(LibraryFolder::mostly_default(path, i, &library.apps));
+        }
+        setup_libraryfolders_file(&apps_dir, library_folders)?;
 
+        let steam_dir = SteamDir::from_dir(&steam_dir)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
         Ok(TempSteamDir {
-            steam_dir: SteamDir::from_dir(&steam_dir)?,
-            _tmps: tmps,
+            steam_dir,
+            _tmp: None,
         })
     }
 }
 
-fn setup_steamapps_dir(apps_dir: &Path, apps: &[AppFile]) -> Result<(), TestError> {
+fn setup_steamapps_dir(apps_dir: &Path, apps: &[AppFile]) -> io::Result<()> {
     let apps_common_dir = apps_dir.join("common");
     fs::create_dir_all(&apps_common_dir)?;
 
@@ -141,24 +208,11 @@ fn setup_steamapps_dir(apps_dir: &Path, apps: &[AppFile]) -> Result<(), TestErro
 
 fn setup_libraryfolders_file(
     apps_dir: &Path,
-    root_library: LibraryFolder,
-    aux_libraries: &[TempLibrary],
-) -> Result<(), TestError> {
-    let library_folders =
-        iter::once(root_library).chain(aux_libraries.iter().map(|temp_library| {
-            LibraryFolder::mostly_default(
-                temp_library.path.clone(),
-                temp_library.content_id,
-                temp_library.apps.clone(),
-            )
-        }));
-    let inner: BTreeMap<u32, LibraryFolder> = library_folders
-        .into_iter()
-        .enumerate()
-        .map(|(i, f)| (i.try_into().unwrap(), f))
-        .collect();
-    let library_folders_contents =
-        keyvalues_serde::to_string_with_key(&inner, "libraryfolders").unwrap();
+    library_folders: Vec<LibraryFolder>,
+) -> io::Result<()> {
+    let inner: BTreeMap<usize, LibraryFolder> = library_folders.into_iter().enumerate().collect();
+    let library_folders_contents = keyvalues_serde::to_string_with_key(&inner, "libraryfolders")
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
     let library_folders_path = apps_dir.join("libraryfolders.vdf");
     fs::write(library_folders_path, library_folders_contents)?;
 
@@ -177,7 +231,8 @@ struct LibraryFolder {
 }
 
 impl LibraryFolder {
-    fn mostly_default(path: PathBuf, contentid: i32, apps: BTreeMap<u32, u64>) -> Self {
+    fn mostly_default(path: PathBuf, contentid: i32, apps: &[AppFile]) -> Self {
+        let apps: BTreeMap<_, _> = apps.iter().map(|app| (app.id, app.size_on_disk)).collect();
         let totalsize = apps.values().sum();
         Self {
             path,
@@ -191,50 +246,30 @@ impl LibraryFolder {
     }
 }
 
+#[derive(Default)]
 pub struct TempLibrary {
-    content_id: i32,
-    path: PathBuf,
-    apps: BTreeMap<u32, u64>,
-    _tmp: TempDir,
+    apps: Vec<AppFile>,
 }
 
-impl TryFrom<AppFile> for TempLibrary {
-    type Error = TestError;
-
-    fn try_from(app: AppFile) -> Result<Self, Self::Error> {
-        Self::builder().app(app).finish()
+impl From<AppFile> for TempLibrary {
+    fn from(app: AppFile) -> Self {
+        Self::default().app(app)
     }
 }
 
-impl TryFrom<SampleApp> for TempLibrary {
-    type Error = TestError;
-
-    fn try_from(sample_app: SampleApp) -> Result<Self, Self::Error> {
-        Self::try_from(AppFile::from(sample_app))
+impl From<SampleApp> for TempLibrary {
+    fn from(sample_app: SampleApp) -> Self {
+        Self::from(AppFile::from(sample_app))
     }
 }
 
 impl TempLibrary {
-    pub fn builder() -> TempLibraryBuilder {
-        TempLibraryBuilder::default()
-    }
-}
-
-#[derive(Default)]
-#[must_use]
-pub struct TempLibraryBuilder {
-    apps: Vec<AppFile>,
-}
-
-impl TempLibraryBuilder {
-    fn app(mut self, app: AppFile) -> Self {
+    pub fn app(mut self, app: AppFile) -> Self {
         self.apps.push(app);
         self
     }
 
-    fn finish(self) -> Result<TempLibrary, TestError> {
-        let tmp = TempDir::new()?;
-        let root_dir = tmp.path().join("test-library");
+    fn write(&self, root_dir: &Path) -> io::Result<()> {
         let apps_dir = root_dir.join("steamapps");
         fs::create_dir_all(&apps_dir)?;
 
@@ -244,15 +279,7 @@ impl TempLibraryBuilder {
             include_str!("../../tests/assets/libraryfolder.vdf"),
         )?;
 
-        setup_steamapps_dir(&apps_dir, &self.apps)?;
-        let apps = self.apps.iter().map(|app| (app.id, 0)).collect();
-
-        Ok(TempLibrary {
-            content_id: 1234,
-            path: root_dir,
-            apps,
-            _tmp: tmp,
-        })
+        setup_steamapps_dir(&apps_dir, &self.apps)
     }
 }
 
@@ -260,19 +287,31 @@ pub struct AppFile {
     id: u32,
     install_dir: String,
     contents: String,
+    size_on_disk: u64,
 }
 
 impl From<SampleApp> for AppFile {
     fn from(sample: SampleApp) -> Self {
-        Self {
-            id: sample.id(),
-            install_dir: sample.install_dir().to_owned(),
-            contents: sample.contents().to_owned(),
-        }
+        Self::new(sample.id(), sample.install_dir(), sample.contents())
     }
 }
 
 impl AppFile {
+    pub fn new(id: u32, install_dir: impl Into<String>, contents: impl Into<String>) -> Self {
+        Self {
+            id,
+            install_dir: install_dir.into(),
+            contents: contents.into(),
+            size_on_disk: 0,
+        }
+    }
+
+    /// The app's size as listed in `libraryfolders.vdf`. Defaults to `0`
+    pub fn size_on_disk(mut self, size_on_disk: u64) -> Self {
+        self.size_on_disk = size_on_disk;
+        self
+    }
+
     fn file_name(&self) -> String {
         format!("appmanifest_{}.acf", self.id)
     }
@@ -325,6 +364,18 @@ impl SampleApp {
     }
 }
 
+pub enum SampleConfig {
+    Linux,
+}
+
+impl SampleConfig {
+    pub const fn data(&self) -> &'static str {
+        match self {
+            Self::Linux => include_str!("../../tests/sample_data/config.vdf"),
+        }
+    }
+}
+
 pub enum SampleShortcuts {
     JustGogMoonlighter,
 }
@@ -1,7 +1,7 @@
 pub mod helpers;
 #[cfg(test)]
 mod legacy;
-mod temp;
+pub(crate) mod temp;
 #[cfg(test)]
 mod tests;
 #[cfg(test)]
@@ -13,7 +13,8 @@ pub type TestResult = Result<(), TestError>;
 pub mod prelude {
     pub use super::{
         helpers::{
-            expect_test_env, AppFile, SampleApp, SampleShortcuts, TempLibrary, TempSteamDir,
+            expect_test_env, AppFile, SampleApp, SampleConfig, SampleShortcuts, TempLibrary,
+            TempSteamDir,
         },
         TestError, TestResult,
     };
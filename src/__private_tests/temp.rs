@@ -3,15 +3,13 @@
 //! I want to use temporary directories in doctests, but that works against your public API.
 //! Luckily all the functionality we need is very easy to replicate
 
-use std::{collections, env, fs, hash, path};
-
-use super::TestError;
+use std::{collections, env, fs, hash, io, path};
 
 #[derive(Debug)]
 pub struct TempDir(Option<path::PathBuf>);
 
 impl TempDir {
-    pub fn new() -> Result<Self, TestError> {
+    pub fn new() -> io::Result<Self> {
         let mut dir = env::temp_dir();
         let random_name = format!("steamlocate-test-{:x}", random_seed());
         dir.push(random_name);
@@ -1,5 +1,9 @@
+use std::fs;
+
+use crate::{CachedSteamDir, Library};
+
 use super::{
-    helpers::{SampleApp, TempSteamDir},
+    helpers::{expect_test_env, SampleApp, SampleConfig, SampleShortcuts, TempSteamDir},
     TestResult,
 };
 
@@ -16,3 +20,1527 @@ fn app_lastupdated_casing() -> TestResult {
 
     Ok(())
 }
+
+#[test]
+fn has_stats() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    let warframe_id = SampleApp::Warframe.id();
+    assert!(!steam_dir.has_stats(gmod_id));
+    assert!(!steam_dir.has_stats(warframe_id));
+
+    // Cached stats schema
+    let stats_cache = steam_dir.path().join("appcache").join("stats");
+    fs::create_dir_all(&stats_cache)?;
+    let schema_name = format!("UserGameStatsSchema_{gmod_id}.bin");
+    fs::write(stats_cache.join(schema_name), [])?;
+    assert!(steam_dir.has_stats(gmod_id));
+
+    // Per-user stats directory
+    let user_stats = steam_dir
+        .path()
+        .join("userdata")
+        .join("123123123")
+        .join(warframe_id.to_string())
+        .join("stats");
+    fs::create_dir_all(user_stats)?;
+    assert!(steam_dir.has_stats(warframe_id));
+
+    Ok(())
+}
+
+#[test]
+fn library_diff() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let previous = steam_dir.libraries()?.next().unwrap()?;
+
+    let steamapps = previous.path().join("steamapps");
+    let resonite = SampleApp::Resonite;
+    let resonite_manifest = steamapps.join(format!("appmanifest_{}.acf", resonite.id()));
+    fs::write(resonite_manifest, resonite.contents())?;
+    let gmod_manifest = steamapps.join(format!("appmanifest_{}.acf", SampleApp::GarrysMod.id()));
+    fs::remove_file(gmod_manifest)?;
+
+    let current = Library::from_dir(previous.path())?;
+    let diff = current.diff(&previous);
+    assert_eq!(diff.added, [resonite.id()]);
+    assert_eq!(diff.removed, [SampleApp::GarrysMod.id()]);
+    assert!(current.diff(&current).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn libraries_deduped() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let library_paths = steam_dir.library_paths()?;
+
+    // List every library a second time through a path that resolves to the same directory
+    let entries: String = library_paths
+        .iter()
+        .chain(library_paths.iter())
+        .enumerate()
+        .map(|(i, path)| {
+            let path = if i < library_paths.len() {
+                path.to_owned()
+            } else {
+                path.join("steamapps").join("..")
+            };
+            let path = path.display().to_string().replace('\\', "\\\\");
+            format!("\"{i}\" {{ \"path\" \"{path}\" \"apps\" {{}} }}\n")
+        })
+        .collect();
+    let libraryfolders_vdf = steam_dir
+        .path()
+        .join("steamapps")
+        .join("libraryfolders.vdf");
+    fs::write(
+        libraryfolders_vdf,
+        format!("\"libraryfolders\" {{\n{entries}}}"),
+    )?;
+
+    assert_eq!(steam_dir.libraries()?.len(), library_paths.len() * 2);
+    let deduped: Vec<_> = steam_dir
+        .libraries_deduped()?
+        .map(|library| library.map(|library| library.path().to_owned()))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(deduped, library_paths);
+
+    Ok(())
+}
+
+#[test]
+fn most_recent_user() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let config_dir = steam_dir.path().join("config");
+    fs::create_dir_all(&config_dir)?;
+    let login_users = config_dir.join("loginusers.vdf");
+
+    // Nobody has logged in on a fresh install
+    assert_eq!(steam_dir.most_recent_user()?, None);
+
+    // Older versions of Steam use lowercase keys
+    fs::write(
+        &login_users,
+        r#"
+"users"
+{
+    "76561197960265729"
+    {
+        "AccountName"        "someone"
+        "mostrecent"        "0"
+    }
+    "76561198083388851"
+    {
+        "AccountName"        "someone_else"
+        "mostrecent"        "1"
+    }
+}
+"#,
+    )?;
+    let (account_id, user_dir) = steam_dir.most_recent_user()?.unwrap();
+    assert_eq!(account_id, 123_123_123);
+    assert_eq!(
+        user_dir,
+        steam_dir.path().join("userdata").join("123123123")
+    );
+
+    // The most recent user doesn't have any userdata
+    fs::write(
+        &login_users,
+        r#""users" { "76561197960265729" { "MostRecent" "1" } }"#,
+    )?;
+    assert_eq!(steam_dir.most_recent_user()?, None);
+
+    Ok(())
+}
+
+#[test]
+fn find_apps() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    let warframe_id = SampleApp::Warframe.id();
+    let missing_id = SampleApp::Resonite.id();
+
+    let found = steam_dir.find_apps(&[gmod_id, warframe_id, missing_id, gmod_id])?;
+    assert_eq!(found.len(), 2);
+    for app_id in [gmod_id, warframe_id] {
+        let (app, library) = &found[&app_id];
+        let (expected_app, expected_library) = steam_dir.find_app(app_id)?.unwrap();
+        assert_eq!(app.app_id, expected_app.app_id);
+        assert_eq!(library.path(), expected_library.path());
+    }
+    assert!(steam_dir.find_apps(&[])?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn cached_steam_dir() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let mut cached = CachedSteamDir::new(steam_dir.clone());
+    let gmod_id = SampleApp::GarrysMod.id();
+
+    let (gmod, library) = cached.find_app(gmod_id)?.unwrap();
+    assert_eq!(gmod.app_id, gmod_id);
+    let gmod_manifest = library
+        .path()
+        .join("steamapps")
+        .join(format!("appmanifest_{gmod_id}.acf"));
+    assert_eq!(cached.apps()?.len(), 3);
+
+    // Changes on disk aren't seen until the cache is invalidated
+    fs::remove_file(gmod_manifest)?;
+    assert!(cached.find_app(gmod_id)?.is_some());
+    cached.invalidate();
+    assert!(cached.find_app(gmod_id)?.is_none());
+    assert_eq!(cached.apps()?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn cached_app_listed_in_another_library() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    let graveyard_keeper_id = SampleApp::GraveyardKeeper.id();
+
+    // Move gmod's manifest out of the library that `libraryfolders.vdf` still lists it under
+    let (_, second_library) = steam_dir.find_app(graveyard_keeper_id)?.unwrap();
+    let manifest_name = format!("appmanifest_{gmod_id}.acf");
+    fs::rename(
+        steam_dir.path().join("steamapps").join(&manifest_name),
+        second_library.path().join("steamapps").join(&manifest_name),
+    )?;
+
+    let mut cached = CachedSteamDir::new(steam_dir.clone());
+    let apps = cached.apps()?;
+    let (gmod, library) = apps
+        .iter()
+        .find(|(app, _)| app.app_id == gmod_id)
+        .expect("gmod is installed in the second library");
+    assert_eq!(gmod.app_id, gmod_id);
+    assert_eq!(library.path(), second_library.path());
+    assert_eq!(apps.len(), 3);
+    let (_, library) = cached.find_app(gmod_id)?.unwrap();
+    assert_eq!(library.path(), second_library.path());
+
+    Ok(())
+}
+
+#[test]
+fn backup_manifest() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    let (gmod, library) = steam_dir.find_app(gmod_id)?.unwrap();
+    assert!(!gmod.from_backup);
+
+    let manifest = library
+        .path()
+        .join("steamapps")
+        .join(format!("appmanifest_{gmod_id}.acf"));
+    let mut backup = manifest.clone().into_os_string();
+    backup.push(".bak");
+    fs::rename(&manifest, backup)?;
+
+    let library = Library::from_dir(library.path())?;
+    assert!(library.app_ids().contains(&gmod_id));
+    let gmod = library.app(gmod_id).unwrap()?;
+    assert_eq!(gmod.app_id, gmod_id);
+    assert!(gmod.from_backup);
+
+    Ok(())
+}
+
+#[test]
+fn lossy_manifest() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    let (gmod, library) = steam_dir.find_app(gmod_id)?.unwrap();
+    assert!(!gmod.lossy_decoded);
+
+    // Corrupt a byte in the app's name
+    let manifest = library
+        .path()
+        .join("steamapps")
+        .join(format!("appmanifest_{gmod_id}.acf"));
+    let contents = fs::read_to_string(&manifest)?.replace("Garry's Mod", "Garry's M\u{1}d");
+    let corrupted: Vec<u8> = contents
+        .bytes()
+        .map(|b| if b == 1 { 0xff } else { b })
+        .collect();
+    fs::write(&manifest, corrupted)?;
+
+    let gmod = library.app(gmod_id).unwrap()?;
+    assert!(gmod.lossy_decoded);
+    assert_eq!(gmod.name.as_deref(), Some("Garry's M\u{fffd}d"));
+    assert_eq!(gmod.install_dir, "GarrysMod");
+
+    Ok(())
+}
+
+#[test]
+fn validate() -> TestResult {
+    // The default test env doesn't include a `config.vdf`
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let report = steam_dir.validate();
+    assert!(!report.is_healthy());
+    assert!(report.steamapps_dir.passed());
+    assert!(report.library_folders.passed());
+    assert_eq!(report.libraries.len(), 2);
+    assert!(report.libraries.iter().all(|check| check.passed()));
+    assert!(!report.config.passed());
+    assert!(report.userdata_dir.passed());
+
+    // A listed library that's gone missing
+    let library_path = report.libraries[1].path.clone();
+    fs::remove_dir_all(&library_path)?;
+    let report = steam_dir.validate();
+    assert!(!report.libraries[1].passed());
+    assert!(report.to_string().contains("[fail] library"));
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn foreign_windows_library_path() -> TestResult {
+    use crate::Error;
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let libraryfolders_vdf = steam_dir
+        .path()
+        .join("steamapps")
+        .join("libraryfolders.vdf");
+    fs::write(
+        libraryfolders_vdf,
+        r#""libraryfolders" { "0" { "path" "D:\\SteamLibrary" "apps" {} } }"#,
+    )?;
+
+    let err = steam_dir.libraries()?.next().unwrap().unwrap_err();
+    assert!(matches!(err, Error::InvalidSteamDir(_)), "{err:?}");
+    assert!(err.to_string().contains("different OS"));
+
+    // Nonexistent Unix paths are still reported as I/O errors
+    let err = Library::from_dir("/definitely/not/a/library".as_ref()).unwrap_err();
+    assert!(matches!(err, Error::Io { .. }), "{err:?}");
+
+    Ok(())
+}
+
+#[test]
+fn shortcuts_with_tag() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+
+    assert_eq!(steam_dir.shortcuts()?.count(), 1);
+    assert_eq!(steam_dir.shortcuts()?.with_tag("Favorites").count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn shortcuts_skip_bad_files() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    let userdata = steam_dir.path().join("userdata");
+    let contents = SampleShortcuts::JustGogMoonlighter.data();
+    for (user_id, contents) in [("1", &[][..]), ("2", &contents[..contents.len() / 2])] {
+        let config_dir = userdata.join(user_id).join("config");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(config_dir.join("shortcuts.vdf"), contents)?;
+    }
+
+    // The empty file has no shortcuts and the truncated one doesn't stop the others from being read
+    let (shortcuts, errors): (Vec<_>, Vec<_>) = steam_dir.shortcuts()?.partition(Result::is_ok);
+    assert_eq!(shortcuts.len(), 1);
+    assert_eq!(errors.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn shortcuts_skip_special_user_dirs() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    let userdata = steam_dir.path().join("userdata");
+    // Only real accounts get read, so none of these lead to duplicate shortcuts
+    for dir in ["0", "ac", "anonymous", "+42", "00"] {
+        let config_dir = userdata.join(dir).join("config");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("shortcuts.vdf"),
+            SampleShortcuts::JustGogMoonlighter.data(),
+        )?;
+    }
+
+    let shortcuts = steam_dir.shortcuts()?.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(shortcuts.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn shortcuts_for_user() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    let other_user = steam_dir.path().join("userdata").join("42").join("config");
+    fs::create_dir_all(&other_user)?;
+    fs::write(
+        other_user.join("shortcuts.vdf"),
+        SampleShortcuts::JustGogMoonlighter.data(),
+    )?;
+    fs::create_dir_all(steam_dir.path().join("userdata").join("7"))?;
+
+    assert_eq!(steam_dir.shortcuts()?.count(), 2);
+    let shortcuts = steam_dir
+        .shortcuts_for_user(123_123_123)?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(shortcuts.len(), 1);
+    assert_eq!(shortcuts[0].app_name, "Moonlighter");
+    assert_eq!(steam_dir.shortcuts_for_user(42)?.count(), 1);
+    // A user without any shortcuts has none while a missing user is an error
+    assert_eq!(steam_dir.shortcuts_for_user(7)?.count(), 0);
+    assert!(steam_dir.shortcuts_for_user(8).is_err());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn from_dir_follows_symlinks() -> TestResult {
+    use crate::SteamDir;
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let root_link = steam_dir.path().parent().unwrap().join("root");
+    std::os::unix::fs::symlink(steam_dir.path(), &root_link)?;
+
+    let linked_steam_dir = SteamDir::from_dir(&root_link)?;
+    assert_eq!(linked_steam_dir.path(), steam_dir.path());
+    assert_eq!(
+        linked_steam_dir.library_paths()?,
+        steam_dir.library_paths()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn configured_accounts() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+
+    let accounts = steam_dir.configured_accounts()?;
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts["someone"], 76_561_197_960_265_729);
+    assert_eq!(accounts["someone_else"], 76_561_198_083_388_851);
+
+    Ok(())
+}
+
+#[test]
+fn language() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    assert_eq!(steam_dir.language()?.as_deref(), Some("english"));
+
+    // A config without a language set
+    let config_path = steam_dir.path().join("config").join("config.vdf");
+    let contents = fs::read_to_string(&config_path)?.replace("\"language\"\t\t\"english\"", "");
+    fs::write(&config_path, contents)?;
+    assert_eq!(steam_dir.language()?, None);
+
+    Ok(())
+}
+
+#[test]
+fn bandwidth_settings() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    let defaults = steam_dir.bandwidth_settings()?;
+    assert_eq!(defaults.download_throttle_kbps, None);
+    assert!(!defaults.allow_downloads_during_gameplay);
+
+    let config_path = steam_dir.config_dir().join("config.vdf");
+    let original = fs::read_to_string(&config_path)?;
+    let with_settings = |throttle: &str| {
+        original.replace(
+            "\"language\"\t\t\"english\"",
+            &format!(
+                "\"language\"\t\t\"english\"\n\"DownloadThrottleKbps\" \"{throttle}\"\n\
+                \"AllowDownloadsDuringGameplay\" \"1\"",
+            ),
+        )
+    };
+    fs::write(&config_path, with_settings("5000"))?;
+    let settings = steam_dir.bandwidth_settings()?;
+    assert_eq!(settings.download_throttle_kbps, Some(5_000));
+    assert!(settings.allow_downloads_during_gameplay);
+    // `0` means unlimited
+    fs::write(&config_path, with_settings("0"))?;
+    assert_eq!(steam_dir.bandwidth_settings()?.download_throttle_kbps, None);
+
+    Ok(())
+}
+
+#[test]
+fn app_by_install_dir() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let sample_app = SampleApp::GarrysMod;
+    let (_, library) = steam_dir.find_app(sample_app.id())?.unwrap();
+
+    let app = library
+        .app_by_install_dir(sample_app.install_dir())
+        .unwrap()?;
+    assert_eq!(app.app_id, sample_app.id());
+    assert!(library.app_by_install_dir("NotARealGame").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn snapshot() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+
+    let snapshot = steam_dir.snapshot(false)?;
+    assert_eq!(snapshot.path, steam_dir.path());
+    assert_eq!(snapshot.libraries.len(), 2);
+    assert!(snapshot
+        .libraries
+        .iter()
+        .all(|library| library.apps.is_none()));
+
+    let snapshot = steam_dir.snapshot(true)?;
+    let gmod_id = SampleApp::GarrysMod.id();
+    let gmod = snapshot
+        .libraries
+        .iter()
+        .flat_map(|library| library.apps.iter().flatten())
+        .find(|app| app.app_id == gmod_id)
+        .unwrap();
+    assert_eq!(gmod.name.as_deref(), Some("Garry's Mod"));
+
+    // Round-trips through a serde data format
+    let serialized = keyvalues_serde::to_string_with_key(&snapshot, "SteamSnapshot")?;
+    let deserialized: crate::SteamSnapshot = keyvalues_serde::from_str(&serialized)?;
+    assert_eq!(deserialized, snapshot);
+
+    Ok(())
+}
+
+#[test]
+fn app_install_dir() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let sample_app = SampleApp::GarrysMod;
+
+    let app_dir = steam_dir.app_install_dir(sample_app.id())?.unwrap();
+    assert!(app_dir.ends_with(sample_app.install_dir()));
+    assert_eq!(steam_dir.app_install_dir(SampleApp::Resonite.id())?, None);
+
+    // Listed, but the install directory is gone
+    fs::remove_dir_all(app_dir)?;
+    assert_eq!(steam_dir.app_install_dir(sample_app.id())?, None);
+
+    Ok(())
+}
+
+#[test]
+fn listed_app_missing_manifest() -> TestResult {
+    use crate::Error;
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    let (_, library) = steam_dir.find_app(gmod_id)?.unwrap();
+    let gmod_manifest = library
+        .path()
+        .join("steamapps")
+        .join(format!("appmanifest_{gmod_id}.acf"));
+    fs::remove_file(gmod_manifest)?;
+
+    // `libraryfolders.vdf` still lists the app, so it gets reported as missing
+    let library = steam_dir
+        .libraries()?
+        .map(|library| library.unwrap())
+        .find(|library| library.listed_without_manifest().contains(&gmod_id))
+        .unwrap();
+    assert!(!library.app_ids().contains(&gmod_id));
+    let err = library.app(gmod_id).unwrap().unwrap_err();
+    assert!(matches!(err, Error::MissingExpectedApp { app_id } if app_id == gmod_id));
+    let err = library.apps().find_map(Result::err).unwrap();
+    assert!(matches!(err, Error::MissingExpectedApp { app_id } if app_id == gmod_id));
+    assert!(!steam_dir.app_ids()?.contains(&gmod_id));
+    // while a library read straight from disk doesn't include it at all
+    assert!(!Library::from_dir(library.path())?
+        .app_ids()
+        .contains(&gmod_id));
+    // and looking it up treats it as not installed
+    assert!(steam_dir.find_app(gmod_id)?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn cancellable_scans() -> TestResult {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use crate::Error;
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let cancel = AtomicBool::new(false);
+
+    let mut libraries = steam_dir.libraries_cancellable(&cancel)?;
+    let library = libraries.next().unwrap()?;
+    cancel.store(true, Ordering::Relaxed);
+    assert!(matches!(libraries.next(), Some(Err(Error::Cancelled))));
+    assert!(libraries.next().is_none());
+
+    let mut apps = library.apps_cancellable(&cancel);
+    assert!(matches!(apps.next(), Some(Err(Error::Cancelled))));
+    assert!(apps.next().is_none());
+
+    cancel.store(false, Ordering::Relaxed);
+    let num_apps = library.apps_cancellable(&cancel).count();
+    assert_eq!(num_apps, library.app_ids().len());
+
+    Ok(())
+}
+
+#[test]
+fn client_version() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    assert_eq!(steam_dir.client_version()?, None);
+
+    let package_dir = steam_dir.path().join("package");
+    fs::create_dir_all(&package_dir)?;
+    let platform = if cfg!(windows) {
+        "win32"
+    } else if cfg!(target_os = "macos") {
+        "osx"
+    } else {
+        "ubuntu12"
+    };
+    let manifest = |version: &str| {
+        format!(
+            r#""{platform}"
+{{
+    "version"        "{version}"
+    "bootstrapperversion"        "1708646544"
+}}"#
+        )
+    };
+    fs::write(
+        package_dir.join("steam_client_aaa_other.manifest"),
+        manifest("1"),
+    )?;
+    fs::write(
+        package_dir.join(format!("steam_client_{platform}.manifest")),
+        manifest("1714854927"),
+    )?;
+    assert_eq!(steam_dir.client_version()?.as_deref(), Some("1714854927"));
+
+    Ok(())
+}
+
+#[test]
+fn uninstall_plan() -> TestResult {
+    use crate::Error;
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    let (_, library) = steam_dir.find_app(gmod_id)?.unwrap();
+
+    let plan = library.uninstall_plan(gmod_id)?;
+    assert!(plan.shared_with.is_empty());
+    assert_eq!(plan.freed_size(), 3_875_126_726 + 281_149_259);
+    assert!(plan.manifest_path.ends_with("appmanifest_4000.acf"));
+
+    // Add an app that relies on one of gmod's depots
+    let addon_manifest = r#"
+"AppState"
+{
+	"appid"		"4010"
+	"installdir"		"GarrysModAddon"
+	"SharedDepots"
+	{
+		"4001"		"4000"
+	}
+}
+"#;
+    let steamapps = library.path().join("steamapps");
+    fs::write(steamapps.join("appmanifest_4010.acf"), addon_manifest)?;
+    let library = Library::from_dir(library.path())?;
+
+    let plan = library.uninstall_plan(gmod_id)?;
+    assert_eq!(plan.shared_with, [4010]);
+    assert_eq!(plan.owned_depots.keys().collect::<Vec<_>>(), [&4003]);
+    assert_eq!(plan.freed_size(), 281_149_259);
+    assert_eq!(plan.install_dir, steamapps.join("common").join("GarrysMod"));
+
+    let err = library.uninstall_plan(1).unwrap_err();
+    assert!(matches!(err, Error::MissingExpectedApp { app_id: 1 }));
+
+    Ok(())
+}
+
+#[test]
+fn compat_tool_mapping() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+
+    let mapping = steam_dir.compat_tool_mapping()?;
+    let default = &mapping[&0];
+    assert_eq!(default.name.as_deref(), Some("proton_experimental"));
+    assert_eq!(default.config(), None);
+    assert_eq!(default.priority, Some(75));
+    let gmod = &mapping[&SampleApp::GarrysMod.id()];
+    assert_eq!(gmod.config.as_deref(), Some(""));
+    assert_eq!(gmod.config(), None);
+    assert_eq!(gmod.priority, Some(250));
+    let warframe = &mapping[&SampleApp::Warframe.id()];
+    assert_eq!(warframe.config(), Some("wined3d"));
+    assert_eq!(warframe.priority, Some(250));
+
+    Ok(())
+}
+
+#[test]
+fn nested_config() -> TestResult {
+    let temp_steam_dir = TempSteamDir::builder()
+        .nested_config(SampleConfig::Linux)
+        .finish()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    assert!(!steam_dir.config_dir().join("config.vdf").exists());
+
+    assert_eq!(steam_dir.compat_tool_mapping()?.len(), 3);
+    assert_eq!(steam_dir.language()?.as_deref(), Some("english"));
+    let report = steam_dir.validate();
+    assert!(report.config.passed(), "{report:?}");
+    assert!(report.config.path.ends_with("steam/config/config.vdf"));
+
+    // The usual location wins when both exist
+    fs::create_dir_all(steam_dir.config_dir())?;
+    fs::write(
+        steam_dir.config_dir().join("config.vdf"),
+        r#""InstallConfigStore" { "Software" { "Valve" { "Steam" { } } } }"#,
+    )?;
+    assert!(steam_dir.compat_tool_mapping()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn effective_compat_tool() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    let tool_name = |app_id| -> crate::Result<Option<String>> {
+        Ok(steam_dir
+            .effective_compat_tool(app_id)?
+            .and_then(|tool| tool.name))
+    };
+
+    assert_eq!(
+        tool_name(SampleApp::GarrysMod.id())?.as_deref(),
+        Some("proton_8")
+    );
+    // Falls back to the global default
+    assert_eq!(
+        tool_name(SampleApp::GraveyardKeeper.id())?.as_deref(),
+        Some("proton_experimental"),
+    );
+
+    // Without a default only explicitly mapped apps get a tool
+    let config_path = steam_dir.config_dir().join("config.vdf");
+    let contents = fs::read_to_string(&config_path)?.replace(
+        "\"name\"\t\t\"proton_experimental\"\n\t\t\t\t\t\t\"config\"\t\t\"\"",
+        "\"name\"\t\t\"\"\n\t\t\t\t\t\t\"config\"\t\t\"\"",
+    );
+    fs::write(&config_path, contents)?;
+    assert_eq!(tool_name(SampleApp::GraveyardKeeper.id())?, None);
+    assert_eq!(
+        tool_name(SampleApp::GarrysMod.id())?.as_deref(),
+        Some("proton_8")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn library_app_ids_sorted() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let library = steam_dir.libraries()?.next().unwrap()?;
+    let steamapps = library.path().join("steamapps");
+    for app_id in [9, 1_000_000, 500] {
+        let manifest = format!("\"AppState\"\n{{\n\t\"appid\"\t\t\"{app_id}\"\n}}\n");
+        fs::write(
+            steamapps.join(format!("appmanifest_{app_id}.acf")),
+            manifest,
+        )?;
+    }
+
+    let library = Library::from_dir(library.path())?;
+    let mut sorted = library.app_ids().to_owned();
+    sorted.sort_unstable();
+    assert_eq!(library.app_ids(), sorted);
+    assert!(library.app_ids().starts_with(&[9, 500]));
+
+    Ok(())
+}
+
+#[test]
+fn games_skip_tools() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let library = steam_dir.libraries()?.next().unwrap()?;
+    let proton_manifest = r#"
+"AppState"
+{
+	"appid"		"1493710"
+	"name"		"Proton Experimental"
+	"installdir"		"Proton - Experimental"
+}
+"#;
+    let manifest_path = library.path().join("steamapps/appmanifest_1493710.acf");
+    fs::write(manifest_path, proton_manifest)?;
+
+    let app_ids = |apps: Vec<(crate::App, Library)>| -> Vec<u32> {
+        apps.into_iter().map(|(app, _)| app.app_id).collect()
+    };
+    let apps = app_ids(steam_dir.apps()?.collect::<Result<_, _>>()?);
+    assert!(apps.contains(&1_493_710));
+    assert_eq!(apps.len(), 4);
+    let games = app_ids(steam_dir.games()?.collect::<Result<_, _>>()?);
+    assert!(!games.contains(&1_493_710));
+    assert_eq!(games.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn write_library_paths() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let libraryfolders_vdf = steam_dir
+        .path()
+        .join("steamapps")
+        .join("libraryfolders.vdf");
+    let original_paths = steam_dir.library_paths()?;
+
+    // Updating an existing library keeps its other info
+    let library = steam_dir.libraries()?.next().unwrap()?;
+    let trimmed = Library::from_dir_with_apps(library.path(), vec![SampleApp::GarrysMod.id()]);
+    steam_dir.write_library_paths(&[trimmed])?;
+    let contents = fs::read_to_string(&libraryfolders_vdf)?;
+    assert!(contents.contains("update_clean_bytes_tally"));
+    assert!(contents.contains("\"79799828443\""));
+    assert_eq!(steam_dir.library_paths()?, original_paths);
+    // The temporary file gets renamed over the original
+    let steamapps = libraryfolders_vdf.parent().unwrap();
+    assert!(!steamapps.join("libraryfolders.vdf.tmp").exists());
+
+    // while new libraries get appended
+    let new_path = steam_dir.path().parent().unwrap().join("NewLibrary");
+    fs::create_dir_all(new_path.join("steamapps"))?;
+    let new_library = Library::from_dir_with_apps(&new_path, vec![SampleApp::Resonite.id()]);
+    steam_dir.write_library_paths(&[new_library])?;
+    let mut paths = steam_dir.library_paths()?;
+    assert_eq!(paths.pop(), Some(new_path.clone()));
+    assert_eq!(paths, original_paths);
+    let new_library = steam_dir.libraries()?.next_back().unwrap()?;
+    // Nothing's installed there yet, so the app is only listed
+    assert!(new_library.app_ids().is_empty());
+    assert_eq!(
+        new_library.listed_without_manifest(),
+        [SampleApp::Resonite.id()]
+    );
+
+    // and writing it again doesn't duplicate it
+    steam_dir.write_library_paths(&[Library::from_dir_with_apps(&new_path, Vec::new())])?;
+    assert_eq!(steam_dir.library_count()?, original_paths.len() + 1);
+
+    Ok(())
+}
+
+#[test]
+fn scan_apps_collects_errors() -> TestResult {
+    use crate::Error;
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let (_, library) = steam_dir.find_app(SampleApp::GarrysMod.id())?.unwrap();
+    let steamapps = library.path().join("steamapps");
+    fs::write(steamapps.join("appmanifest_1234.acf"), "\"AppState\" {")?;
+    fs::remove_file(steamapps.join("appmanifest_4000.acf"))?;
+
+    let scan = steam_dir.scan_apps()?;
+    assert!(!scan.is_complete());
+    assert_eq!(scan.apps.len(), 2);
+    let mut failed: Vec<_> = scan.errors.iter().map(|(app_id, _)| *app_id).collect();
+    failed.sort_unstable();
+    assert_eq!(failed, [1_234, SampleApp::GarrysMod.id()]);
+    assert!(scan
+        .errors
+        .iter()
+        .any(|(_, err)| matches!(err, Error::MissingExpectedApp { app_id: 4_000 })));
+
+    Ok(())
+}
+
+#[test]
+fn auxiliary_dirs() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    let (_, library) = steam_dir.find_app(gmod_id)?.unwrap();
+    assert_eq!(library.auxiliary_dirs(gmod_id).iter().count(), 0);
+
+    let steamapps = library.path().join("steamapps");
+    let shader_cache = steamapps.join("shadercache").join("4000");
+    let workshop_content = steamapps.join("workshop").join("content").join("4000");
+    fs::create_dir_all(&shader_cache)?;
+    fs::create_dir_all(&workshop_content)?;
+
+    let aux_dirs = library.auxiliary_dirs(gmod_id);
+    assert_eq!(aux_dirs.shader_cache, Some(shader_cache));
+    assert_eq!(aux_dirs.compat_data, None);
+    assert_eq!(aux_dirs.workshop_content, Some(workshop_content));
+    assert_eq!(aux_dirs.iter().count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn cloud_files() -> TestResult {
+    use std::time::{Duration, SystemTime};
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let gmod_id = SampleApp::GarrysMod.id();
+    assert!(steam_dir.cloud_files(gmod_id)?.is_empty());
+
+    let remote_cache = r#"
+"4000"
+{
+	"ChangeNumber"		"12"
+	"ostype"		"-184"
+	"garrysmod/cfg/config.cfg"
+	{
+		"root"		"0"
+		"size"		"1024"
+		"localtime"		"1700000000"
+		"time"		"1700000000"
+		"remotetime"		"1700000001"
+		"sha"		"0123456789abcdef0123456789abcdef01234567"
+		"syncstate"		"1"
+		"persiststate"		"0"
+		"platformstosync2"		"-1"
+	}
+}
+"#;
+    let app_dir = steam_dir.path().join("userdata/123123123/4000");
+    fs::create_dir_all(&app_dir)?;
+    fs::write(app_dir.join("remotecache.vdf"), remote_cache)?;
+    // A corrupt inventory only skips that one user
+    let corrupt_dir = steam_dir.path().join("userdata/44404/4000");
+    fs::create_dir_all(&corrupt_dir)?;
+    fs::write(
+        corrupt_dir.join("remotecache.vdf"),
+        r#""4000" { "ChangeNumber" "#,
+    )?;
+
+    let files = steam_dir.cloud_files(gmod_id)?;
+    assert_eq!(files.len(), 1);
+    let file = &files[0];
+    assert_eq!(file.user_id, 123_123_123);
+    assert_eq!(file.path, "garrysmod/cfg/config.cfg");
+    assert_eq!(file.size, Some(1_024));
+    let epoch_secs = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+    assert_eq!(file.local_time, Some(epoch_secs(1_700_000_000)));
+    assert_eq!(file.remote_time, Some(epoch_secs(1_700_000_001)));
+    assert_eq!(file.sync_state, Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn measured_app_size() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let (gmod, library) = steam_dir.find_app(SampleApp::GarrysMod.id())?.unwrap();
+    let gmod_dir = library.resolve_app_dir(&gmod);
+    assert_eq!(library.measured_app_size(&gmod)?, 0);
+
+    fs::write(gmod_dir.join("hl2.exe"), [0; 100])?;
+    fs::create_dir_all(gmod_dir.join("garrysmod"))?;
+    fs::write(gmod_dir.join("garrysmod").join("data.vpk"), [0; 1_000])?;
+    assert_eq!(library.measured_app_size(&gmod)?, 1_100);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::symlink;
+
+        // Links to files that are already counted and links that loop back are only counted once
+        symlink(gmod_dir.join("hl2.exe"), gmod_dir.join("link.exe"))?;
+        symlink(&gmod_dir, gmod_dir.join("garrysmod").join("loop"))?;
+        symlink(gmod_dir.join("missing"), gmod_dir.join("dangling"))?;
+        assert_eq!(library.measured_app_size(&gmod)?, 1_100);
+
+        // while links to files outside of the install dir count the target's size
+        let outside = library.path().join("outside.bin");
+        fs::write(&outside, [0; 10])?;
+        symlink(&outside, gmod_dir.join("outside.bin"))?;
+        assert_eq!(library.measured_app_size(&gmod)?, 1_110);
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn app_ids_deduped() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let expected = steam_dir.app_ids()?;
+
+    // List the first library a second time through a symlink
+    let library = steam_dir.libraries()?.next().unwrap()?;
+    let link = steam_dir.path().parent().unwrap().join("linked-library");
+    std::os::unix::fs::symlink(library.path(), &link)?;
+    steam_dir.write_library_paths(&[library.with_path(link)])?;
+    assert_eq!(steam_dir.library_count()?, 3);
+
+    assert_eq!(steam_dir.app_ids()?, expected);
+
+    Ok(())
+}
+
+#[test]
+fn app_users() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let user_data = steam_dir.path().join("userdata");
+    assert!(steam_dir.app_users(4_000)?.is_empty());
+
+    let write_local_config = |account_id: &str, contents: &str| {
+        let config_dir = user_data.join(account_id).join("config");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(config_dir.join("localconfig.vdf"), contents)
+    };
+    write_local_config(
+        "123123123",
+        r#""UserLocalConfigStore" { "Software" { "Valve" { "Steam" { "apps" {
+            "4000" { "LastPlayed" "1700000000" }
+            "230410" { }
+        } } } } }"#,
+    )?;
+    // Key casing varies between users
+    write_local_config(
+        "22202",
+        r#""UserLocalConfigStore" { "software" { "valve" { "steam" { "Apps" {
+            "4000" { }
+        } } } } }"#,
+    )?;
+    // and some users never configured any apps
+    write_local_config("33303", r#""UserLocalConfigStore" { "friends" { } }"#)?;
+    // A corrupt config only skips that one user
+    write_local_config("44404", r#""UserLocalConfigStore" { "Software" "#)?;
+    fs::create_dir_all(user_data.join("0").join("config"))?;
+
+    assert_eq!(
+        steam_dir.app_users(4_000)?,
+        [76_561_197_960_287_930, 76_561_198_083_388_851],
+    );
+    assert_eq!(steam_dir.app_users(230_410)?, [76_561_198_083_388_851]);
+    assert!(steam_dir.app_users(599_140)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn vdf_parse_file() -> TestResult {
+    use std::collections::BTreeMap;
+
+    use crate::{error::ParseErrorKind, vdf, Error};
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let path = steam_dir.path().join("tool_manifest.vdf");
+
+    fs::write(
+        &path,
+        "\u{feff}\"Tool\" { \"name\" \"Example\" \"version\" \"3\" }",
+    )?;
+    let tool: BTreeMap<String, String> = vdf::parse_file(&path, "tool")?;
+    assert_eq!(tool["name"], "Example");
+    assert_eq!(tool["version"], "3");
+
+    fs::write(&path, "\"Tool\" {")?;
+    let err = vdf::parse_file::<BTreeMap<String, String>>(&path, "tool").unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            Error::Parse {
+                kind: ParseErrorKind::Custom,
+                error,
+                ..
+            } if error.label() == Some("tool")
+        ),
+        "{err:?}"
+    );
+    assert!(
+        err.to_string().contains("File kind: Custom (tool)"),
+        "{err}"
+    );
+    // Labels don't need to be known ahead of time
+    let file_name = path.file_name().unwrap().to_string_lossy();
+    let err = vdf::parse_file::<BTreeMap<String, String>>(&path, &file_name).unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            Error::Parse {
+                kind: ParseErrorKind::Custom,
+                error,
+                ..
+            } if error.label() == Some("tool_manifest.vdf")
+        ),
+        "{err:?}"
+    );
+
+    let missing = steam_dir.path().join("missing.vdf");
+    let err = vdf::parse_file::<()>(&missing, "tool").unwrap_err();
+    assert!(matches!(err, Error::Io { .. }), "{err:?}");
+
+    Ok(())
+}
+
+#[test]
+fn next_scheduled_update() -> TestResult {
+    use std::time::{Duration, SystemTime};
+
+    use crate::AutoUpdateWindow;
+
+    let temp_steam_dir = TempSteamDir::builder()
+        .app(SampleApp::GraveyardKeeper.into())
+        .config(SampleConfig::Linux)
+        .finish()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    let at = |secs: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+    const HOUR: u64 = 60 * 60;
+    const DAY: u64 = 24 * HOUR;
+
+    // Scheduled for 2023-03-10 14:16:46 UTC which is outside of the 02:00-06:00 window, so it
+    // moves to 02:00 the next day
+    let (mut app, _library) = steam_dir
+        .find_app(SampleApp::GraveyardKeeper.id())?
+        .unwrap();
+    let scheduled = 1_678_457_806;
+    let day = scheduled - scheduled % DAY;
+    assert_eq!(
+        steam_dir.next_scheduled_update(&app, 0)?,
+        Some(at(day + DAY + 2 * HOUR)),
+    );
+    // In UTC-10 it's 04:16 local time which is already within the window
+    assert_eq!(
+        steam_dir.next_scheduled_update(&app, -10 * 60 * 60)?,
+        Some(at(scheduled)),
+    );
+    // In UTC+10 it's 00:16 local time the next day, so it waits until 02:00 local time
+    assert_eq!(
+        steam_dir.next_scheduled_update(&app, 10 * 60 * 60)?,
+        Some(at(day + DAY + 2 * HOUR - 10 * HOUR)),
+    );
+
+    app.scheduled_auto_update = None;
+    assert_eq!(steam_dir.next_scheduled_update(&app, 0)?, None);
+
+    // Windows that span midnight
+    let window = AutoUpdateWindow {
+        enabled: true,
+        start_hour: Some(22),
+        end_hour: Some(3),
+    };
+    assert_eq!(
+        window.next_allowed(at(DAY + 23 * HOUR), 0),
+        at(DAY + 23 * HOUR)
+    );
+    assert_eq!(window.next_allowed(at(DAY + HOUR), 0), at(DAY + HOUR));
+    assert_eq!(
+        window.next_allowed(at(DAY + 3 * HOUR), 0),
+        at(DAY + 22 * HOUR)
+    );
+    let disabled = AutoUpdateWindow {
+        enabled: false,
+        ..window
+    };
+    assert_eq!(
+        disabled.next_allowed(at(DAY + 3 * HOUR), 0),
+        at(DAY + 3 * HOUR)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn find_library_containing() -> TestResult {
+    use crate::Error;
+
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let graveyard_keeper = SampleApp::GraveyardKeeper.id();
+    let (app, library) = steam_dir.find_app(graveyard_keeper)?.unwrap();
+    let save_file = library.resolve_app_dir(&app).join("save.dat");
+    fs::create_dir_all(save_file.parent().unwrap())?;
+    fs::write(&save_file, "")?;
+
+    let found = steam_dir.find_library_containing(&save_file)?.unwrap();
+    assert_eq!(found.app_ids(), [graveyard_keeper]);
+    // Paths within the root library resolve to it
+    let found = steam_dir
+        .find_library_containing(&steam_dir.path().join("steamapps"))?
+        .unwrap();
+    assert!(found.app_ids().contains(&SampleApp::GarrysMod.id()));
+
+    let outside = super::temp::TempDir::new()?;
+    assert!(steam_dir.find_library_containing(outside.path())?.is_none());
+    let err = steam_dir
+        .find_library_containing(&outside.path().join("missing"))
+        .unwrap_err();
+    assert!(matches!(err, Error::Io { .. }), "{err:?}");
+
+    // Paths that go through a symlink still resolve to the library
+    #[cfg(unix)]
+    {
+        let link = outside.path().join("link");
+        std::os::unix::fs::symlink(library.path(), &link)?;
+        let through_link = save_file.strip_prefix(library.path())?;
+        let found = steam_dir
+            .find_library_containing(&link.join(through_link))?
+            .unwrap();
+        assert_eq!(found.app_ids(), [graveyard_keeper]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn strip_verbatim_prefix() {
+    use crate::library::strip_verbatim_prefix as strip;
+
+    assert_eq!(
+        strip(r"\\?\C:\Program Files (x86)\Steam").as_deref(),
+        Some(r"C:\Program Files (x86)\Steam"),
+    );
+    assert_eq!(
+        strip(r"\\?\UNC\server\share\SteamLibrary").as_deref(),
+        Some(r"\\server\share\SteamLibrary"),
+    );
+    // Not verbatim to begin with
+    assert_eq!(strip(r"C:\Program Files (x86)\Steam"), None);
+    // Meaning would change without the prefix
+    assert_eq!(strip(r"\\?\C:\Games\..\Steam"), None);
+    assert_eq!(strip(r"\\?\C:\Games/Steam"), None);
+    assert_eq!(strip(r"\\?\C:\Games\Steam."), None);
+    assert_eq!(strip(r"\\?\C:\Games\nul.txt"), None);
+    assert_eq!(strip(r"\\?\C:\Games\COM1"), None);
+    assert_eq!(strip(r"\\?\Volume{0123}\Steam"), None);
+    // Reserved names only count as whole components
+    assert_eq!(
+        strip(r"\\?\D:\Console\COM10").as_deref(),
+        Some(r"D:\Console\COM10"),
+    );
+}
+
+#[test]
+fn launch_options() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let user_id = 123_123_123;
+    let config_dir = steam_dir
+        .path()
+        .join("userdata")
+        .join(user_id.to_string())
+        .join("config");
+    let gmod_id = SampleApp::GarrysMod.id();
+    assert_eq!(steam_dir.launch_options_for_user(gmod_id, user_id)?, None);
+
+    fs::create_dir_all(&config_dir)?;
+    fs::write(
+        config_dir.join("localconfig.vdf"),
+        r#""UserLocalConfigStore" { "Software" { "Valve" { "Steam" { "apps" {
+            "4000" { "LaunchOptions" "gamemoderun %command% -console" }
+            "230410" { "LaunchOptions" "" }
+        } } } } }"#,
+    )?;
+    fs::write(
+        config_dir.join("shortcuts.vdf"),
+        include_bytes!("../../tests/sample_data/shortcuts.vdf"),
+    )?;
+    assert_eq!(
+        steam_dir
+            .launch_options_for_user(gmod_id, user_id)?
+            .as_deref(),
+        Some("gamemoderun %command% -console"),
+    );
+    assert_eq!(
+        steam_dir.launch_options_for_user(SampleApp::Warframe.id(), user_id)?,
+        None,
+    );
+    // LibreOffice Calc's shortcut
+    assert_eq!(
+        steam_dir
+            .launch_options_for_user(2_492_174_738, user_id)?
+            .as_deref(),
+        Some("--calc"),
+    );
+    assert_eq!(steam_dir.launch_options_for_user(gmod_id, 1)?, None);
+
+    let steam_config_dir = steam_dir.path().join("config");
+    fs::create_dir_all(&steam_config_dir)?;
+    fs::write(
+        steam_config_dir.join("loginusers.vdf"),
+        r#""users" { "76561198083388851" { "MostRecent" "1" } }"#,
+    )?;
+    assert_eq!(
+        steam_dir.launch_options(gmod_id)?.as_deref(),
+        Some("gamemoderun %command% -console"),
+    );
+
+    Ok(())
+}
+
+// Catch any changes that would accidentally make the public types `!Send` or `!Sync`
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<crate::SteamDir>();
+    assert_send_sync::<crate::CachedSteamDir>();
+    assert_send_sync::<Library>();
+    assert_send_sync::<crate::App>();
+    assert_send_sync::<crate::Shortcut>();
+    assert_send_sync::<crate::Error>();
+    assert_send_sync::<crate::ScanResult>();
+    assert_send_sync::<crate::SteamSnapshot>();
+    assert_send_sync::<crate::ValidationReport>();
+    assert_send_sync::<crate::library::Iter>();
+    assert_send_sync::<crate::shortcut::Iter>();
+    assert_send_sync::<crate::app::Iter<'static>>();
+    assert_send_sync::<crate::cancel::Cancellable<'static, crate::library::Iter>>();
+};
+
+#[test]
+fn reversed_iterators() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+
+    let paths = |libraries: &mut dyn Iterator<Item = crate::Result<Library>>| {
+        libraries
+            .map(|library| library.map(|library| library.path().to_owned()))
+            .collect::<crate::Result<Vec<_>>>()
+    };
+    let mut forward = paths(&mut steam_dir.libraries()?)?;
+    assert_eq!(forward.len(), 2);
+    forward.reverse();
+    assert_eq!(paths(&mut steam_dir.libraries()?.rev())?, forward);
+
+    let library = steam_dir.libraries()?.next().unwrap()?;
+    let app_ids = library
+        .apps()
+        .rev()
+        .map(|app| app.map(|app| app.app_id))
+        .collect::<crate::Result<Vec<_>>>()?;
+    assert_eq!(
+        app_ids,
+        [SampleApp::Warframe.id(), SampleApp::GarrysMod.id()]
+    );
+
+    // Both ends can be consumed from the same iterator
+    let mut libraries = steam_dir.libraries()?;
+    assert!(libraries.next_back().is_some());
+    assert_eq!(libraries.len(), 1);
+    assert!(libraries.next().is_some());
+    assert!(libraries.next_back().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn orphaned_dirs() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let library = steam_dir.libraries()?.next().unwrap()?;
+    assert!(library.orphaned_dirs()?.is_empty());
+
+    let common = library.common_path();
+    fs::create_dir(common.join("LeftoverGame"))?;
+    // Stray files aren't directories that can be cleaned up
+    fs::write(common.join("stray.txt"), "")?;
+    assert_eq!(library.orphaned_dirs()?, [common.join("LeftoverGame")]);
+
+    // Removing an app's manifest orphans its directory
+    let steamapps = library.path().join("steamapps");
+    let (gmod, _) = steam_dir.find_app(SampleApp::GarrysMod.id())?.unwrap();
+    fs::remove_file(steamapps.join(format!("appmanifest_{}.acf", gmod.app_id)))?;
+    assert_eq!(
+        library.orphaned_dirs()?,
+        [library.resolve_app_dir(&gmod), common.join("LeftoverGame")],
+    );
+
+    // A manifest that fails to parse can't rule out its directory
+    fs::write(
+        steamapps.join(format!("appmanifest_{}.acf", SampleApp::Warframe.id())),
+        "\"AppState\" {",
+    )?;
+    assert!(library.orphaned_dirs().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn all_entries() -> TestResult {
+    use crate::Entry;
+
+    let temp_steam_dir = TempSteamDir::builder()
+        .app(SampleApp::GarrysMod.into())
+        .shortcuts(SampleShortcuts::JustGogMoonlighter)
+        .finish()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+
+    let entries = steam_dir.all_entries()?.collect::<Result<Vec<_>, _>>()?;
+    let [gmod, moonlighter] = entries.as_slice() else {
+        panic!("Expected one app and one shortcut: {entries:#?}");
+    };
+    assert!(matches!(gmod, Entry::App(..)));
+    assert_eq!(gmod.id(), SampleApp::GarrysMod.id().into());
+    assert_eq!(gmod.name(), "Garry's Mod");
+    assert_eq!(gmod.launch_url(), "steam://rungameid/4000");
+    assert!(matches!(moonlighter, Entry::Shortcut(_)));
+    assert_eq!(moonlighter.id(), 17_731_278_641_934_893_056);
+    assert_eq!(moonlighter.name(), "Moonlighter");
+    assert_eq!(
+        moonlighter.launch_url(),
+        "steam://rungameid/17731278641934893056"
+    );
+
+    // Fresh installs without any `userdata` still list their apps
+    fs::remove_dir_all(steam_dir.userdata_dir())?;
+    assert_eq!(steam_dir.all_entries()?.count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn skins() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    assert!(steam_dir.available_skins()?.is_empty());
+
+    let skins_dir = steam_dir.path().join("skins");
+    fs::create_dir_all(skins_dir.join("Pressure"))?;
+    fs::create_dir_all(skins_dir.join("Metro"))?;
+    fs::write(skins_dir.join("Readme.txt"), "")?;
+    assert_eq!(steam_dir.available_skins()?, ["Metro", "Pressure"]);
+
+    let config_dir = steam_dir.config_dir();
+    fs::create_dir_all(&config_dir)?;
+    let write_config = |steam: &str| {
+        let vdf = format!(
+            r#""InstallConfigStore" {{ "Software" {{ "Valve" {{ "Steam" {{ {steam} }} }} }} }}"#
+        );
+        fs::write(config_dir.join("config.vdf"), vdf)
+    };
+
+    write_config(r#""SkinV5" "Metro" "SkinV4" "Pressure""#)?;
+    assert_eq!(steam_dir.active_skin()?.as_deref(), Some("Metro"));
+    // The old key is only used when the new one is unset
+    write_config(r#""SkinV5" "" "SkinV4" "Pressure""#)?;
+    assert_eq!(steam_dir.active_skin()?.as_deref(), Some("Pressure"));
+    write_config(r#""SkinV5" """#)?;
+    assert_eq!(steam_dir.active_skin()?, None);
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn iterators_dont_hold_dir_handles() -> TestResult {
+    let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    let steam_dir = temp_steam_dir.steam_dir();
+    let open_fds = || fs::read_dir("/proc/self/fd").map(Iterator::count);
+
+    const NUM_ITERS: usize = 256;
+    let before = open_fds()?;
+    let iters = (0..NUM_ITERS)
+        .map(|_| steam_dir.shortcuts())
+        .collect::<Result<Vec<_>, _>>()?;
+    // Other tests running in parallel can open some files of their own, but nowhere near one per
+    // iterator
+    assert!(open_fds()? < before + NUM_ITERS / 2);
+
+    for iter in iters {
+        assert_eq!(iter.count(), 1);
+    }
+    Ok(())
+}
+
+#[test]
+fn from_dir_shallow() -> TestResult {
+    let temp_steam_dir = expect_test_env();
+    let steam_dir = temp_steam_dir.steam_dir();
+    let steamapps = steam_dir.path().join("steamapps");
+    let gmod_warframe = [SampleApp::GarrysMod.id(), SampleApp::Warframe.id()];
+    assert_eq!(
+        Library::from_dir(steam_dir.path())?.app_ids(),
+        gmod_warframe
+    );
+    assert_eq!(
+        Library::from_dir_shallow(steam_dir.path())?.app_ids(),
+        gmod_warframe,
+    );
+
+    // Names that only look close to a manifest are never picked up
+    for name in ["appmanifest_.acf", "appmanifest_1.txt", "manifest_1.acf"] {
+        fs::write(steamapps.join(name), "")?;
+    }
+    // Only the full check notices that this isn't a manifest
+    fs::create_dir(steamapps.join("appmanifest_1.acf"))?;
+    assert_eq!(
+        Library::from_dir(steam_dir.path())?.app_ids(),
+        gmod_warframe
+    );
+    assert_eq!(
+        Library::from_dir_shallow(steam_dir.path())?.app_ids(),
+        [1, SampleApp::GarrysMod.id(), SampleApp::Warframe.id()],
+    );
+    let library = steam_dir.libraries()?.next().unwrap()?;
+    assert_eq!(library.app_ids()[0], 1);
+
+    // Symlinked manifests still count as files
+    #[cfg(unix)]
+    {
+        let gmod_manifest =
+            steamapps.join(format!("appmanifest_{}.acf", SampleApp::GarrysMod.id()));
+        std::os::unix::fs::symlink(gmod_manifest, steamapps.join("appmanifest_2.acf"))?;
+        assert!(Library::from_dir(steam_dir.path())?.app_ids().contains(&2));
+    }
+
+    Ok(())
+}
@@ -13,17 +13,19 @@
 //!   - Iterates over all of the apps contained in this library
 
 use std::{
+    cmp::Ordering,
     collections::BTreeMap,
-    fs,
+    fmt, fs, iter,
     path::{Path, PathBuf},
     slice, time,
 };
 
 use crate::{
     error::{ParseError, ParseErrorKind},
-    Error, Library, Result,
+    trace, Error, Library, Result,
 };
 
+use keyvalues_parser::{Obj, Vdf};
 use serde::{Deserialize, Deserializer};
 
 /// An [`Iterator`] over a [`Library`]'s [`App`]s
@@ -31,16 +33,27 @@ use serde::{Deserialize, Deserializer};
 /// Returned from calling [`Library::apps()`]
 pub struct Iter<'library> {
     library: &'library Library,
-    app_ids: slice::Iter<'library, u32>,
+    app_ids: iter::Chain<slice::Iter<'library, u32>, slice::Iter<'library, u32>>,
 }
 
 impl<'library> Iter<'library> {
     pub(crate) fn new(library: &'library Library) -> Self {
         Self {
             library,
-            app_ids: library.app_ids().iter(),
+            // Apps listed in `libraryfolders.vdf` that are missing their manifest come last, and
+            // get reported as `Error::MissingExpectedApp` by `Library::app()`
+            app_ids: library
+                .app_ids()
+                .iter()
+                .chain(library.listed_without_manifest()),
         }
     }
+
+    fn app(&self, app_id: u32) -> Result<App> {
+        self.library
+            .app(app_id)
+            .unwrap_or(Err(Error::MissingExpectedApp { app_id }))
+    }
 }
 
 impl Iterator for Iter<'_> {
@@ -48,12 +61,14 @@ impl Iterator for Iter<'_> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let app_id = *self.app_ids.next()?;
-        if let some_res @ Some(_) = self.library.app(app_id) {
-            some_res
-        } else {
-            // We use the listing from libraryfolders, so all apps should be accounted for
-            Some(Err(Error::MissingExpectedApp { app_id }))
-        }
+        Some(self.app(app_id))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let app_id = *self.app_ids.next_back()?;
+        Some(self.app(app_id))
     }
 }
 
@@ -152,31 +167,799 @@ pub struct App {
     pub target_build_id: Option<u64>,
     pub auto_update_behavior: Option<AutoUpdateBehavior>,
     pub allow_other_downloads_while_running: Option<AllowOtherDownloadsWhileRunning>,
-    pub scheduled_auto_update: Option<ScheduledAutoUpdate>,
+    /// When Steam has scheduled the app to be automatically updated
+    ///
+    /// Steam writes a scheduled time of `0` (the Unix epoch) to indicate that no update is
+    /// scheduled, so both that and a missing value are represented as [`None`]
+    #[serde(default, deserialize_with = "de_scheduled_time")]
+    pub scheduled_auto_update: Option<time::SystemTime>,
     pub full_validate_before_next_update: Option<bool>,
     pub full_validate_after_next_update: Option<bool>,
     #[serde(default)]
     pub installed_depots: BTreeMap<u64, Depot>,
     #[serde(default)]
     pub staged_depots: BTreeMap<u64, Depot>,
+    /// Flat string values from the manifest's `UserConfig` section (nested values are skipped)
     #[serde(default)]
     pub user_config: BTreeMap<String, String>,
+    /// Flat string values from the manifest's `MountedConfig` section (nested values are skipped)
     #[serde(default)]
     pub mounted_config: BTreeMap<String, String>,
     #[serde(default)]
     pub install_scripts: BTreeMap<u64, PathBuf>,
     #[serde(default)]
     pub shared_depots: BTreeMap<u64, u64>,
+    /// Any top-level scalar values from the manifest that aren't modeled by another field
+    ///
+    /// This allows for reading values that Steam has added since this version of the crate was
+    /// released (e.g. `"SizeOnDiskStaged"`). Nested (non-scalar) values are not included
+    #[serde(skip)]
+    pub extra: BTreeMap<String, String>,
+    /// Whether this was read from a backup manifest (`appmanifest_<APP_ID>.acf.bak`)
+    ///
+    /// Steam can leave behind a backup while it's in the middle of writing a new manifest. This
+    /// is only set when the primary manifest was missing at the time it was read
+    #[serde(skip)]
+    pub from_backup: bool,
+    /// Whether the manifest contained invalid UTF-8 that had to be replaced while reading it
+    ///
+    /// This can happen with corrupted installs. The invalid bytes are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`, so any values that contained them may be inaccurate
+    #[serde(skip)]
+    pub lossy_decoded: bool,
 }
 
+/// All of the top-level manifest keys that [`App`] deserializes into its own fields
+const MODELED_KEYS: &[&str] = &[
+    "appid",
+    "installdir",
+    "name",
+    "LastOwner",
+    "Universe",
+    "LauncherPath",
+    "StateFlags",
+    "LastUpdated",
+    "lastupdated",
+    "UpdateResult",
+    "SizeOnDisk",
+    "buildid",
+    "BytesToDownload",
+    "BytesDownloaded",
+    "BytesToStage",
+    "BytesStaged",
+    "StagingSize",
+    "TargetBuildID",
+    "AutoUpdateBehavior",
+    "AllowOtherDownloadsWhileRunning",
+    "ScheduledAutoUpdate",
+    "FullValidateBeforeNextUpdate",
+    "FullValidateAfterNextUpdate",
+    "InstalledDepots",
+    "StagedDepots",
+    "UserConfig",
+    "MountedConfig",
+    "InstallScripts",
+    "SharedDepots",
+];
+
 impl App {
     pub(crate) fn new(manifest: &Path) -> Result<Self> {
-        let contents = fs::read_to_string(manifest).map_err(|io| Error::io(io, manifest))?;
-        keyvalues_serde::from_str(&contents)
-            .map_err(|err| Error::parse(ParseErrorKind::App, ParseError::from_serde(err), manifest))
+        trace::trace!("reading app manifest {}", manifest.display());
+        let bytes = fs::read(manifest).map_err(|io| Error::io(io, manifest))?;
+        // A single bad byte shouldn't make the whole app unreadable, so fall back to a lossy
+        // decode and flag it instead
+        let (contents, lossy_decoded) = match String::from_utf8(bytes) {
+            Ok(contents) => (contents, false),
+            Err(err) => {
+                trace::debug!("app manifest {} isn't valid UTF-8", manifest.display());
+                (String::from_utf8_lossy(err.as_bytes()).into_owned(), true)
+            }
+        };
+        let mut app = Self::from_manifest_str(&contents)
+            .map_err(|err| Error::parse(ParseErrorKind::App, err, manifest))?;
+        app.lossy_decoded = lossy_decoded;
+        Ok(app)
+    }
+
+    fn from_manifest_str(contents: &str) -> std::result::Result<Self, ParseError> {
+        // Manifests saved by some Windows text editors start with a byte order mark
+        let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents);
+        // NOTE: The root key (typically `AppState`) is skipped over entirely when deserializing, so
+        // variations in its casing don't need any special handling
+        let mut vdf = Vdf::parse(contents).map_err(ParseError::from_parser)?;
+        if let Some(root) = vdf.value.get_mut_obj() {
+            drop_nested_config_values(root);
+        }
+        let extra = vdf
+            .value
+            .get_obj()
+            .map(unmodeled_scalars)
+            .unwrap_or_default();
+        let mut app: Self = keyvalues_serde::from_vdf(vdf).map_err(ParseError::from_serde)?;
+        app.extra = extra;
+        Ok(app)
+    }
+
+    /// The app's [`name`][Self::name], falling back to its [`install_dir`][Self::install_dir]
+    ///
+    /// Some apps (mostly tools) don't have a name in their manifest, so this gives a consistent
+    /// label to show instead
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// assert_eq!(gmod.display_name(), "Garry's Mod");
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.install_dir)
+    }
+
+    /// The app's [`size_on_disk`][Self::size_on_disk] formatted as a human readable string
+    ///
+    /// Uses binary units (e.g. `GiB`). See [`human_bytes()`][crate::fmt::human_bytes] for details
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// assert_eq!(gmod.size_on_disk_human().unwrap(), "3.9 GiB");
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn size_on_disk_human(&self) -> Option<String> {
+        self.size_on_disk.map(crate::fmt::human_bytes)
+    }
+
+    /// The beta branch that the app is set to use, if any
+    ///
+    /// Read from the `BetaKey` entry in [`user_config`][Self::user_config] (matched
+    /// case-insensitively). Returns [`None`] when the app is on the default (public) branch
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// match gmod.beta_branch() {
+    ///     Some(branch) => println!("Garry's Mod is on the {branch} branch"),
+    ///     None => println!("Garry's Mod is on the default branch"),
+    /// }
+    /// # assert_eq!(gmod.beta_branch(), None);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn beta_branch(&self) -> Option<&str> {
+        config_value(&self.user_config, "BetaKey").filter(|branch| !branch.is_empty())
+    }
+
+    /// The minimum age required to play the app, if the manifest records it
+    ///
+    /// This is looked up as a `RequiredAge` entry (matched case-insensitively) at the top level of
+    /// the manifest (see [`extra`][Self::extra]), then in [`user_config`][Self::user_config], and
+    /// finally in [`mounted_config`][Self::mounted_config]. Most manifests don't include it at all
+    /// since the store's age rating and content descriptors live in Steam's binary
+    /// `appcache/appinfo.vdf`, which this crate doesn't parse. Returns [`None`] when unknown
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// if gmod.required_age().is_some_and(|age| age >= 18) {
+    ///     println!("Garry's Mod is for adults only");
+    /// }
+    /// # assert_eq!(gmod.required_age(), None);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn required_age(&self) -> Option<u32> {
+        [&self.extra, &self.user_config, &self.mounted_config]
+            .into_iter()
+            .find_map(|config| config_value(config, "RequiredAge"))
+            .and_then(|age| age.trim().parse().ok())
+    }
+
+    /// The app's [`state_flags`][Self::state_flags] as a display-friendly string
+    ///
+    /// Each set flag is displayed by name and joined with `" | "` e.g.
+    /// `"UpdateRequired | FullyInstalled"`. Flags with a value of zero display as `"Invalid"`
+    /// and missing flags display as `"<none>"`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// assert_eq!(gmod.state_flags_display(), "FullyInstalled");
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn state_flags_display(&self) -> String {
+        match self.state_flags {
+            Some(state_flags) => {
+                let names: Vec<_> = state_flags.flags().map(|flag| flag.to_string()).collect();
+                names.join(" | ")
+            }
+            None => "<none>".to_owned(),
+        }
+    }
+
+    /// A small JSON summary of the app
+    ///
+    /// The output is a single compact object with the following keys, always in this order:
+    ///
+    /// | Key | Type | Value |
+    /// | --- | --- | --- |
+    /// | `app_id` | number | [`app_id`][Self::app_id] |
+    /// | `name` | string or `null` | [`name`][Self::name] |
+    /// | `install_dir` | string | [`install_dir`][Self::install_dir] |
+    /// | `size` | number or `null` | [`size_on_disk`][Self::size_on_disk] in bytes |
+    /// | `state_flags` | array of strings | Each of the [`state_flags`][Self::state_flags] displayed by name (empty when missing) |
+    /// | `installed` | boolean | Whether the [`StateFlag::FullyInstalled`] flag is set |
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// assert_eq!(
+    ///     gmod.to_json(),
+    ///     r#"{"app_id":4000,"name":"Garry's Mod","install_dir":"GarrysMod","size":4152333499,"state_flags":["FullyInstalled"],"installed":true}"#,
+    /// );
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn to_json(&self) -> String {
+        let flags: Vec<_> = self
+            .state_flags
+            .map(|state_flags| state_flags.flags().collect())
+            .unwrap_or_default();
+
+        let mut json = format!("{{\"app_id\":{},\"name\":", self.app_id);
+        match &self.name {
+            Some(name) => push_json_str(&mut json, name),
+            None => json.push_str("null"),
+        }
+        json.push_str(",\"install_dir\":");
+        push_json_str(&mut json, &self.install_dir);
+        json.push_str(",\"size\":");
+        match self.size_on_disk {
+            Some(size) => json.push_str(&size.to_string()),
+            None => json.push_str("null"),
+        }
+        json.push_str(",\"state_flags\":[");
+        for (i, flag) in flags.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            push_json_str(&mut json, &flag.to_string());
+        }
+        let installed = flags.contains(&StateFlag::FullyInstalled);
+        json.push_str(&format!("],\"installed\":{installed}}}"));
+        json
+    }
+
+    /// How long ago the app was last updated according to [`last_updated`][Self::last_updated]
+    ///
+    /// Returns [`None`] when the manifest doesn't include a last updated time. A time that's in
+    /// the future (e.g. from clock skew) is clamped to a [`Duration`][time::Duration] of zero
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// let ago = gmod.updated_ago().unwrap();
+    /// println!("Updated {} days ago", ago.as_secs() / 60 / 60 / 24);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn updated_ago(&self) -> Option<time::Duration> {
+        let last_updated = self.last_updated?;
+        let ago = time::SystemTime::now()
+            .duration_since(last_updated)
+            .unwrap_or_default();
+        Some(ago)
+    }
+
+    /// Whether this app looks like a tool (Proton, the Steam Linux Runtime, redistributables,
+    /// etc.) rather than a game
+    ///
+    /// Manifests don't record an app's type, so this is a heuristic. An app is considered a tool
+    /// when any of the following hold
+    ///
+    /// - Its ID is one of a set of well known tool IDs
+    /// - Its install directory matches one used by Proton or the Steam Linux Runtime
+    /// - It's missing a store [`name`][Self::name]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// assert!(!gmod.is_tool());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn is_tool(&self) -> bool {
+        const TOOL_INSTALL_DIR_PREFIXES: &[&str] = &["Proton ", "Proton-", "SteamLinuxRuntime"];
+
+        KNOWN_TOOL_IDS.contains(&self.app_id)
+            || TOOL_INSTALL_DIR_PREFIXES
+                .iter()
+                .any(|prefix| self.install_dir.starts_with(prefix))
+            || self.name.is_none()
+    }
+
+    /// Whether this app looks like a dedicated server rather than a game
+    ///
+    /// Like [`App::is_tool()`] this is a heuristic since manifests don't record an app's type. An
+    /// app is considered a dedicated server when any of the following hold
+    ///
+    /// - Its ID is one of a set of well known dedicated server IDs
+    /// - Its [`name`][Self::name] or install directory ends with "Dedicated Server"
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// assert!(!gmod.is_dedicated_server());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn is_dedicated_server(&self) -> bool {
+        let ends_with_suffix = |s: &str| s.to_ascii_lowercase().ends_with("dedicated server");
+
+        KNOWN_DEDICATED_SERVER_IDS.contains(&self.app_id)
+            || self.name.as_deref().is_some_and(ends_with_suffix)
+            || ends_with_suffix(&self.install_dir)
+    }
+
+    /// Whether the app is on the [`Public`][Universe::Public] universe
+    ///
+    /// This is `false` for apps on the beta, internal, or dev universes along with apps whose
+    /// manifest doesn't specify a [`universe`][Self::universe] at all
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// assert!(gmod.is_public());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn is_public(&self) -> bool {
+        self.universe == Some(Universe::Public)
+    }
+
+    /// Whether the installed build is out of date compared to `latest_build_id`
+    ///
+    /// `latest_build_id` is typically the latest build ID fetched from Steam's web API. The app is
+    /// considered outdated when either
+    ///
+    /// - Steam already has an update pending ([`target_build_id`][Self::target_build_id] is set
+    ///   and differs from [`build_id`][Self::build_id])
+    /// - `latest_build_id` is newer than [`build_id`][Self::build_id]
+    ///
+    /// Returns [`None`] when the installed [`build_id`][Self::build_id] is unknown
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// # /*
+    /// let latest_build_id = /* Fetch the latest build ID from Steam */;
+    /// # */
+    /// # let latest_build_id = gmod.build_id.unwrap() + 1;
+    /// if gmod.is_outdated(latest_build_id) == Some(true) {
+    ///     println!("An update is available for Garry's Mod");
+    /// }
+    /// # assert_eq!(gmod.is_outdated(latest_build_id), Some(true));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn is_outdated(&self, latest_build_id: u64) -> Option<bool> {
+        let build_id = self.build_id?;
+        // Steam uses a target build ID of `0` when nothing is pending
+        let update_pending = self
+            .target_build_id
+            .is_some_and(|target| target != 0 && target != build_id);
+        Some(update_pending || latest_build_id > build_id)
+    }
+
+    /// The update that Steam has pending for the app, if any
+    ///
+    /// An update is pending when [`target_build_id`][Self::target_build_id] is set (and non-zero)
+    /// and differs from [`build_id`][Self::build_id]. This is typically an update that's been
+    /// downloaded or staged and is waiting on Steam (or the app) to be restarted to get applied
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// if let Some(update) = gmod.pending_update() {
+    ///     println!(
+    ///         "Restart Steam to update from {:?} to {}",
+    ///         update.current_build_id, update.target_build_id,
+    ///     );
+    /// }
+    /// # assert_eq!(gmod.pending_update(), None);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn pending_update(&self) -> Option<PendingUpdate> {
+        // Steam uses a target build ID of `0` when nothing is pending
+        let target_build_id = self
+            .target_build_id
+            .filter(|&target| target != 0 && Some(target) != self.build_id)?;
+        Some(PendingUpdate {
+            current_build_id: self.build_id,
+            target_build_id,
+            bytes_to_stage: self.bytes_to_stage,
+            bytes_staged: self.bytes_staged,
+        })
+    }
+
+    /// Whether the app is fully installed, partially installed, or not installed at all
+    ///
+    /// Apps with the [`StateFlag::FullyInstalled`] flag are always [`Complete`] even if they have
+    /// an update pending (see [`App::pending_update()`] for that). Otherwise an app is
+    /// [`Partial`] if it has any [installed][Self::installed_depots] or
+    /// [staged][Self::staged_depots] depots or any [bytes to download][Self::bytes_to_download],
+    /// which is what's left behind by an interrupted install
+    ///
+    /// [`Complete`]: InstallCompleteness::Complete
+    /// [`Partial`]: InstallCompleteness::Partial
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// use steamlocate::app::InstallCompleteness;
+    ///
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// assert_eq!(gmod.install_completeness(), InstallCompleteness::Complete);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn install_completeness(&self) -> InstallCompleteness {
+        let fully_installed = self
+            .state_flags
+            .is_some_and(|flags| flags.flags().any(|flag| flag == StateFlag::FullyInstalled));
+        if fully_installed {
+            InstallCompleteness::Complete
+        } else if !self.installed_depots.is_empty()
+            || !self.staged_depots.is_empty()
+            || self.bytes_to_download.is_some_and(|bytes| bytes > 0)
+        {
+            InstallCompleteness::Partial
+        } else {
+            InstallCompleteness::NotInstalled
+        }
+    }
+
+    /// Compares apps by [`name`][Self::name] for use with e.g. [`slice::sort_by()`]
+    ///
+    /// Names are compared case-insensitively and apps without a name sort last. Ties are broken
+    /// by [`app_id`][Self::app_id], so the order is always consistent
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// use steamlocate::App;
+    ///
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let mut apps = Vec::new();
+    /// for library in steam_dir.libraries()? {
+    ///     for app in library?.apps() {
+    ///         apps.push(app?);
+    ///     }
+    /// }
+    /// apps.sort_by(App::cmp_by_name);
+    /// # let names: Vec<_> = apps.iter().map(|app| app.display_name()).collect();
+    /// # assert_eq!(names, ["Garry's Mod", "Graveyard Keeper", "Warframe"]);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn cmp_by_name(&self, other: &App) -> Ordering {
+        let name = |app: &App| app.name.as_ref().map(|name| name.to_lowercase());
+        cmp_none_last(name(self), name(other))
+            .then_with(|| self.name.cmp(&other.name))
+            .then_with(|| self.app_id.cmp(&other.app_id))
+    }
+
+    /// Compares apps by [`size_on_disk`][Self::size_on_disk] from smallest to largest
+    ///
+    /// Apps without a size sort last. Ties are broken by [`app_id`][Self::app_id]. Use
+    /// `|a, b| b.cmp_by_size(a)` to sort from largest to smallest instead, which also puts apps
+    /// without a size first
+    pub fn cmp_by_size(&self, other: &App) -> Ordering {
+        cmp_none_last(self.size_on_disk, other.size_on_disk)
+            .then_with(|| self.app_id.cmp(&other.app_id))
+    }
+
+    /// Compares apps by [`last_updated`][Self::last_updated] from oldest to newest
+    ///
+    /// Apps without a last updated time sort last. Ties are broken by [`app_id`][Self::app_id]
+    pub fn cmp_by_last_updated(&self, other: &App) -> Ordering {
+        cmp_none_last(self.last_updated, other.last_updated)
+            .then_with(|| self.app_id.cmp(&other.app_id))
+    }
+
+    /// Whether `other` is fundamentally the same installed app as this one
+    ///
+    /// Unlike [`PartialEq`] this only compares the stable fields ([`app_id`][Self::app_id],
+    /// [`install_dir`][Self::install_dir], [`name`][Self::name], [`universe`][Self::universe], and
+    /// [`build_id`][Self::build_id]), so transient state like download progress is ignored
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// let mut downloading = gmod.clone();
+    /// downloading.bytes_downloaded = Some(1_024);
+    /// assert_ne!(gmod, downloading);
+    /// assert!(gmod.same_identity(&downloading));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn same_identity(&self, other: &App) -> bool {
+        self.app_id == other.app_id
+            && self.install_dir == other.install_dir
+            && self.name == other.name
+            && self.universe == other.universe
+            && self.build_id == other.build_id
+    }
+
+    /// The summed [`size`][Depot::size] of all of the [installed depots][Self::installed_depots]
+    ///
+    /// This can differ from [`size_on_disk`][Self::size_on_disk] which also accounts for any
+    /// shared or staged content
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// println!("Garry's Mod's depots take up {} bytes", gmod.installed_depots_size());
+    /// # assert_eq!(gmod.installed_depots_size(), 3_875_126_726 + 281_149_259);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn installed_depots_size(&self) -> u64 {
+        self.installed_depots.values().map(|depot| depot.size).sum()
+    }
+
+    /// Iterates over all of the app's [installed][Self::installed_depots],
+    /// [staged][Self::staged_depots], and [shared][Self::shared_depots] depots
+    ///
+    /// Installed depots come first, then staged, then shared, with each group sorted by depot ID.
+    /// A depot that's in the middle of an update can be both installed and staged, in which case
+    /// it's yielded once for each state
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// use steamlocate::app::DepotState;
+    ///
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// for entry in gmod.depots() {
+    ///     match (entry.state, entry.depot, entry.shared_from) {
+    ///         (DepotState::Shared, _, Some(app_id)) => {
+    ///             println!("Depot {} is shared from app {app_id}", entry.id);
+    ///         }
+    ///         (state, Some(depot), _) => {
+    ///             println!("Depot {} is {state:?} with manifest {}", entry.id, depot.manifest);
+    ///         }
+    ///         _ => unreachable!(),
+    ///     }
+    /// }
+    /// # assert_eq!(gmod.depots().count(), 2);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn depots(&self) -> impl Iterator<Item = DepotEntry> + '_ {
+        let with_depot = |state| {
+            move |(&id, &depot): (&u64, &Depot)| DepotEntry {
+                id,
+                state,
+                depot: Some(depot),
+                shared_from: None,
+            }
+        };
+        let installed = self
+            .installed_depots
+            .iter()
+            .map(with_depot(DepotState::Installed));
+        let staged = self
+            .staged_depots
+            .iter()
+            .map(with_depot(DepotState::Staged));
+        let shared = self.shared_depots.iter().map(|(&id, &app_id)| DepotEntry {
+            id,
+            state: DepotState::Shared,
+            depot: None,
+            shared_from: Some(app_id),
+        });
+        installed.chain(staged).chain(shared)
+    }
+
+    /// The number of depots that this app uses from other apps
+    ///
+    /// _See [`shared_depots`][Self::shared_depots]_
+    pub fn shared_depot_count(&self) -> usize {
+        self.shared_depots.len()
+    }
+
+    /// A stable fingerprint of the app's installed content
+    ///
+    /// Two installs with the same app ID, build, and installed depot manifests will have the same
+    /// fingerprint regardless of where (or on which machine) they're installed, which makes it a
+    /// cheap way to tell that two installs are identical without walking their files
+    ///
+    /// The fingerprint is the CRC-64/XZ checksum of the following values, each encoded as
+    /// little-endian bytes in order:
+    ///
+    /// 1. [`app_id`][Self::app_id] as a `u32`
+    /// 2. [`build_id`][Self::build_id] as a `u64` (`0` when missing)
+    /// 3. For each of the [`installed_depots`][Self::installed_depots] sorted by depot ID: the
+    ///    depot ID followed by its [`manifest`][Depot::manifest], both as `u64`s
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, _library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// println!("Garry's Mod's content fingerprint: {:016x}", gmod.content_fingerprint());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn content_fingerprint(&self) -> u64 {
+        let algorithm = crc::Crc::<u64>::new(&crc::CRC_64_XZ);
+        let mut digest = algorithm.digest();
+        digest.update(&self.app_id.to_le_bytes());
+        digest.update(&self.build_id.unwrap_or_default().to_le_bytes());
+        // `BTreeMap` iterates in sorted order already
+        for (depot_id, depot) in &self.installed_depots {
+            digest.update(&depot_id.to_le_bytes());
+            digest.update(&depot.manifest.to_le_bytes());
+        }
+        digest.finalize()
     }
 }
 
+/// App IDs of commonly installed tools that show up alongside games in a library
+const KNOWN_TOOL_IDS: &[u32] = &[
+    228_980,   // Steamworks Common Redistributables
+    858_280,   // Proton 3.7
+    930_400,   // Proton 3.7 Beta
+    961_940,   // Proton 3.16
+    996_510,   // Proton 3.16 Beta
+    1_054_830, // Proton 4.2
+    1_070_560, // Steam Linux Runtime
+    1_113_280, // Proton 4.11
+    1_161_040, // Proton BattlEye Runtime
+    1_245_040, // Proton 5.0
+    1_391_110, // Steam Linux Runtime - Soldier
+    1_420_170, // Proton 5.13
+    1_493_710, // Proton Experimental
+    1_580_130, // Proton 6.3
+    1_628_350, // Steam Linux Runtime - Sniper
+    1_826_330, // Proton EasyAntiCheat Runtime
+    1_887_720, // Proton 7.0
+    2_180_100, // Proton Hotfix
+    2_230_260, // Proton Next
+    2_348_590, // Proton 8.0
+    2_805_730, // Proton 9.0
+];
+
+/// App IDs of dedicated servers whose names don't give them away
+const KNOWN_DEDICATED_SERVER_IDS: &[u32] = &[
+    90,        // Half-Life Dedicated Server
+    205,       // Source Dedicated Server
+    740,       // Counter-Strike Global Offensive - Dedicated Server
+    4_020,     // Garry's Mod Dedicated Server
+    222_860,   // Left 4 Dead 2 Dedicated Server
+    232_250,   // Team Fortress 2 Dedicated Server
+    232_330,   // Counter-Strike: Source Dedicated Server
+    258_550,   // Rust Dedicated Server
+    343_050,   // Don't Starve Together Dedicated Server
+    376_030,   // ARK: Survival Evolved Dedicated Server
+    380_870,   // Project Zomboid Dedicated Server
+    896_660,   // Valheim Dedicated Server
+    1_690_800, // Satisfactory Dedicated Server
+    1_829_350, // V Rising Dedicated Server
+    2_394_010, // Palworld Dedicated Server
+];
+
+/// Looks up `key` in one of the app's config maps, ignoring the key's casing
+fn config_value<'config>(
+    config: &'config BTreeMap<String, String>,
+    key: &str,
+) -> Option<&'config str> {
+    config
+        .iter()
+        .find(|(config_key, _)| config_key.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.as_str())
+}
+
+fn unmodeled_scalars(obj: &Obj) -> BTreeMap<String, String> {
+    obj.iter()
+        .filter(|(key, _)| !MODELED_KEYS.contains(&key.as_ref()))
+        .filter_map(|(key, values)| {
+            let value = values.first()?.get_str()?;
+            Some((key.to_string(), value.to_owned()))
+        })
+        .collect()
+}
+
 macro_rules! impl_deserialize_from_u64 {
     ( $ty_name:ty ) => {
         impl<'de> Deserialize<'de> for $ty_name {
@@ -219,6 +1002,51 @@ impl From<u64> for Universe {
 
 impl_deserialize_from_u64!(Universe);
 
+/// Removes any non-scalar values from the `UserConfig` and `MountedConfig` sections
+///
+/// Both are modeled as flat string maps, but some installs include nested entries like
+/// `DisabledDLC` that would otherwise fail the whole manifest
+fn drop_nested_config_values(root: &mut Obj<'_>) {
+    for section in ["UserConfig", "MountedConfig"] {
+        let Some(values) = root.get_mut(section) else {
+            continue;
+        };
+        for config in values.iter_mut().filter_map(|value| value.get_mut_obj()) {
+            config.retain(|_, values| {
+                values.retain(|value| value.get_str().is_some());
+                !values.is_empty()
+            });
+        }
+    }
+}
+
+/// Like [`Ord::cmp()`] for [`Option`]s, but with [`None`] ordered after any [`Some`]
+fn cmp_none_last<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Appends `s` to `json` as a quoted and escaped JSON string
+fn push_json_str(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if u32::from(c) < 0x20 => json.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub struct StateFlags(pub u64);
@@ -330,6 +1158,37 @@ pub enum StateFlag {
     Unknown(u8),
 }
 
+impl fmt::Display for StateFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Invalid => "Invalid",
+            Self::Uninstalled => "Uninstalled",
+            Self::UpdateRequired => "UpdateRequired",
+            Self::FullyInstalled => "FullyInstalled",
+            Self::Encrypted => "Encrypted",
+            Self::Locked => "Locked",
+            Self::FilesMissing => "FilesMissing",
+            Self::AppRunning => "AppRunning",
+            Self::FilesCorrupt => "FilesCorrupt",
+            Self::UpdateRunning => "UpdateRunning",
+            Self::UpdatePaused => "UpdatePaused",
+            Self::UpdateStarted => "UpdateStarted",
+            Self::Uninstalling => "Uninstalling",
+            Self::BackupRunning => "BackupRunning",
+            Self::Reconfiguring => "Reconfiguring",
+            Self::Validating => "Validating",
+            Self::AddingFiles => "AddingFiles",
+            Self::Preallocating => "Preallocating",
+            Self::Downloading => "Downloading",
+            Self::Staging => "Staging",
+            Self::Committing => "Committing",
+            Self::UpdateStopping => "UpdateStopping",
+            Self::Unknown(offset) => return write!(f, "Unknown({offset})"),
+        };
+        f.write_str(name)
+    }
+}
+
 // More info: https://github.com/lutris/lutris/blob/master/docs/steam.rst
 impl StateFlag {
     fn from_bit_offset(offset: u8) -> Self {
@@ -371,6 +1230,21 @@ where
     Ok(maybe_time)
 }
 
+// Like `de_time_as_secs_from_unix_epoch()` except that `0` means "not scheduled"
+fn de_scheduled_time<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<time::SystemTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match <Option<u64>>::deserialize(deserializer)? {
+        None | Some(0) => Ok(None),
+        Some(secs) => time_as_secs_from_unix_epoch(secs)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("Exceeded max time")),
+    }
+}
+
 fn time_as_secs_from_unix_epoch(secs: u64) -> Option<time::SystemTime> {
     let offset = time::Duration::from_secs(secs);
     time::SystemTime::UNIX_EPOCH.checked_add(offset)
@@ -420,30 +1294,6 @@ impl From<u64> for AutoUpdateBehavior {
 
 impl_deserialize_from_u64!(AutoUpdateBehavior);
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(test, derive(serde::Serialize))]
-pub enum ScheduledAutoUpdate {
-    Zero,
-    Time(time::SystemTime),
-}
-
-impl<'de> Deserialize<'de> for ScheduledAutoUpdate {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let sched_auto_upd = match u64::deserialize(deserializer)? {
-            0 => Self::Zero,
-            secs => {
-                let time = time_as_secs_from_unix_epoch(secs)
-                    .ok_or_else(|| serde::de::Error::custom("Exceeded max time"))?;
-                Self::Time(time)
-            }
-        };
-        Ok(sched_auto_upd)
-    }
-}
-
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
 #[cfg_attr(test, derive(serde::Serialize))]
 #[non_exhaustive]
@@ -454,12 +1304,139 @@ pub struct Depot {
     pub dlc_app_id: Option<u64>,
 }
 
+/// A single depot of an app
+///
+/// _See [`App::depots()`]_
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct DepotEntry {
+    /// The depot's ID
+    pub id: u64,
+    /// Which of the app's depot lists the depot came from
+    pub state: DepotState,
+    /// The depot's data for [`Installed`][DepotState::Installed] and
+    /// [`Staged`][DepotState::Staged] depots
+    pub depot: Option<Depot>,
+    /// The ID of the app that provides a [`Shared`][DepotState::Shared] depot
+    pub shared_from: Option<u64>,
+}
+
+/// The state of a [`DepotEntry`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepotState {
+    /// From [`App::installed_depots`]
+    Installed,
+    /// From [`App::staged_depots`], so it's downloaded, but not applied yet
+    Staged,
+    /// From [`App::shared_depots`], so it's installed as part of another app
+    Shared,
+}
+
+/// An update that Steam has pending for an app
+///
+/// _See [`App::pending_update()`]_
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PendingUpdate {
+    /// The currently installed build ID ([`App::build_id`])
+    pub current_build_id: Option<u64>,
+    /// The build ID being updated to ([`App::target_build_id`])
+    pub target_build_id: u64,
+    /// The total bytes to stage for the update ([`App::bytes_to_stage`])
+    pub bytes_to_stage: Option<u64>,
+    /// The bytes staged so far ([`App::bytes_staged`])
+    pub bytes_staged: Option<u64>,
+}
+
+/// How much of an app is installed
+///
+/// _See [`App::install_completeness()`]_
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstallCompleteness {
+    /// Nothing has been installed yet
+    NotInstalled,
+    /// Some content is installed, but the install was never finished (e.g. it was interrupted)
+    Partial,
+    /// The app is fully installed
+    Complete,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn app_from_manifest_str(s: &str) -> App {
-        keyvalues_serde::from_str(s).unwrap()
+        App::from_manifest_str(s).unwrap()
+    }
+
+    /// Captures the field names that serde's derive passes along when deserializing a struct
+    struct FieldNames(Option<&'static [&'static str]>);
+
+    impl<'de> Deserializer<'de> for &mut FieldNames {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: serde::de::Visitor<'de>>(
+            self,
+            _: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("only structs are supported"))
+        }
+
+        fn deserialize_struct<V: serde::de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            self.0 = Some(fields);
+            Err(serde::de::Error::custom("done"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+            option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+            ignored_any
+        }
+    }
+
+    #[test]
+    fn modeled_keys_match_fields() {
+        let mut field_names = FieldNames(None);
+        let _ = App::deserialize(&mut field_names);
+        let fields = field_names.0.unwrap();
+
+        for field in fields {
+            assert!(
+                MODELED_KEYS.contains(field),
+                "{field:?} is missing from `MODELED_KEYS`"
+            );
+        }
+        // Aliases don't show up in the derived field names
+        const ALIASES: &[&str] = &["lastupdated"];
+        for key in MODELED_KEYS {
+            assert!(
+                fields.contains(key) || ALIASES.contains(key),
+                "{key:?} in `MODELED_KEYS` isn't a field"
+            );
+        }
+    }
+
+    #[test]
+    fn extra_excludes_modeled_keys() {
+        let manifests = [
+            include_str!("../tests/assets/appmanifest_230410.acf"),
+            include_str!("../tests/assets/appmanifest_2519830.acf"),
+            include_str!("../tests/assets/appmanifest_2519830_nested_config.acf"),
+            include_str!("../tests/assets/appmanifest_4000.acf"),
+            include_str!("../tests/assets/appmanifest_4000_comments.acf"),
+            include_str!("../tests/assets/appmanifest_599140.acf"),
+        ];
+        for manifest in manifests {
+            let app = app_from_manifest_str(manifest);
+            for key in app.extra.keys() {
+                assert!(!MODELED_KEYS.contains(&key.as_str()), "{key:?} is modeled");
+            }
+        }
     }
 
     #[test]
@@ -490,6 +1467,460 @@ mod tests {
         insta::assert_ron_snapshot!(app);
     }
 
+    #[test]
+    fn extra() {
+        let manifest = r#"
+"AppState"
+{
+	"appid"		"2519830"
+	"installdir" "Resonite"
+	"lastupdated" "1706645108"
+	"SizeOnDiskStaged" "1234"
+	"SomeNewObject"
+	{
+		"key"		"value"
+	}
+}
+"#;
+
+        let app = app_from_manifest_str(manifest);
+        assert!(app.last_updated.is_some());
+        let expected = BTreeMap::from([("SizeOnDiskStaged".to_owned(), "1234".to_owned())]);
+        assert_eq!(app.extra, expected);
+
+        let manifest = include_str!("../tests/assets/appmanifest_230410.acf");
+        let app = app_from_manifest_str(manifest);
+        assert!(app.extra.is_empty());
+    }
+
+    #[test]
+    fn root_key_casing() {
+        let manifest = include_str!("../tests/assets/appmanifest_230410.acf");
+        let lowercase_root = manifest.replacen("\"AppState\"", "\"appstate\"", 1);
+        assert_ne!(manifest, lowercase_root);
+
+        let app = app_from_manifest_str(manifest);
+        let lowercase_root_app = app_from_manifest_str(&lowercase_root);
+        assert_eq!(app, lowercase_root_app);
+    }
+
+    #[test]
+    fn beta_branch() {
+        let mut app = app_from_manifest_str(include_str!("../tests/assets/appmanifest_230410.acf"));
+        assert_eq!(app.beta_branch(), None);
+
+        app.user_config.insert("betakey".into(), "".into());
+        assert_eq!(app.beta_branch(), None);
+
+        app.user_config
+            .insert("betakey".into(), "public_test".into());
+        assert_eq!(app.beta_branch(), Some("public_test"));
+    }
+
+    #[test]
+    fn required_age() {
+        let mut app = app_from_manifest_str(include_str!("../tests/assets/appmanifest_230410.acf"));
+        assert_eq!(app.required_age(), None);
+
+        app.mounted_config.insert("requiredage".into(), "16".into());
+        assert_eq!(app.required_age(), Some(16));
+        app.user_config.insert("RequiredAge".into(), "18".into());
+        assert_eq!(app.required_age(), Some(18));
+
+        let manifest = include_str!("../tests/assets/appmanifest_230410.acf").replacen(
+            "{",
+            "{\n\t\"RequiredAge\"\t\t\"17\"",
+            1,
+        );
+        let app = app_from_manifest_str(&manifest);
+        assert_eq!(app.extra["RequiredAge"], "17");
+        assert_eq!(app.required_age(), Some(17));
+    }
+
+    #[test]
+    fn updated_ago() {
+        let mut app = app_from_manifest_str(include_str!("../tests/assets/appmanifest_230410.acf"));
+        assert!(app.updated_ago().unwrap() > time::Duration::ZERO);
+
+        let in_the_future = time::SystemTime::now() + time::Duration::from_secs(60 * 60);
+        app.last_updated = Some(in_the_future);
+        assert_eq!(app.updated_ago(), Some(time::Duration::ZERO));
+
+        app.last_updated = None;
+        assert_eq!(app.updated_ago(), None);
+    }
+
+    #[test]
+    fn scheduled_auto_update() {
+        let manifest = r#"
+"AppState"
+{
+	"appid"		"2519830"
+	"installdir" "Resonite"
+	"ScheduledAutoUpdate" "0"
+}
+"#;
+        let app = app_from_manifest_str(manifest);
+        assert_eq!(app.scheduled_auto_update, None);
+
+        let manifest = include_str!("../tests/assets/appmanifest_599140.acf");
+        let app = app_from_manifest_str(manifest);
+        let expected = time::SystemTime::UNIX_EPOCH + time::Duration::from_secs(1_678_457_806);
+        assert_eq!(app.scheduled_auto_update, Some(expected));
+    }
+
+    #[test]
+    fn comments_and_trailing_whitespace() {
+        let manifest = include_str!("../tests/assets/appmanifest_4000.acf");
+        let app = app_from_manifest_str(manifest);
+        let commented = include_str!("../tests/assets/appmanifest_4000_comments.acf");
+        assert_eq!(app_from_manifest_str(commented), app);
+        let with_bom = format!("\u{feff}{manifest}");
+        assert_eq!(app_from_manifest_str(&with_bom), app);
+    }
+
+    #[test]
+    fn is_outdated() {
+        let manifest = include_str!("../tests/assets/appmanifest_230410.acf");
+        let mut app = app_from_manifest_str(manifest);
+        app.build_id = Some(100);
+        app.target_build_id = Some(100);
+        assert_eq!(app.is_outdated(100), Some(false));
+        assert_eq!(app.is_outdated(99), Some(false));
+        assert_eq!(app.is_outdated(101), Some(true));
+
+        // An update is already pending
+        app.target_build_id = Some(101);
+        assert_eq!(app.is_outdated(100), Some(true));
+        app.target_build_id = Some(0);
+        assert_eq!(app.is_outdated(100), Some(false));
+
+        app.build_id = None;
+        assert_eq!(app.is_outdated(100), None);
+    }
+
+    #[test]
+    fn depot_aggregates() {
+        let manifest = include_str!("../tests/assets/appmanifest_2519830.acf");
+        let app = app_from_manifest_str(manifest);
+        assert_eq!(app.installed_depots_size(), 514_493_538 + 587_829_578);
+        assert_eq!(app.shared_depot_count(), 4);
+
+        let manifest = include_str!("../tests/assets/appmanifest_230410.acf");
+        let app = app_from_manifest_str(manifest);
+        assert_eq!(app.installed_depots_size(), 29_070_834_580);
+        assert_eq!(app.shared_depot_count(), 0);
+    }
+
+    #[test]
+    fn depots() {
+        let manifest = include_str!("../tests/assets/appmanifest_2519830.acf");
+        let mut app = app_from_manifest_str(manifest);
+        let staged = Depot {
+            manifest: 1,
+            size: 2,
+            dlc_app_id: None,
+        };
+        app.staged_depots.insert(2_519_831, staged);
+
+        let entries: Vec<_> = app.depots().collect();
+        let count = |state| entries.iter().filter(|e| e.state == state).count();
+        assert_eq!(count(DepotState::Installed), app.installed_depots.len());
+        assert_eq!(count(DepotState::Staged), 1);
+        assert_eq!(count(DepotState::Shared), app.shared_depots.len());
+        assert_eq!(entries.len(), app.installed_depots.len() + 1 + 4);
+
+        let staged_entry = entries
+            .iter()
+            .find(|e| e.state == DepotState::Staged)
+            .unwrap();
+        assert_eq!(staged_entry.id, 2_519_831);
+        assert_eq!(staged_entry.depot, Some(staged));
+        assert_eq!(staged_entry.shared_from, None);
+        for entry in entries.iter().filter(|e| e.state == DepotState::Shared) {
+            assert_eq!(entry.depot, None);
+            assert_eq!(entry.shared_from, app.shared_depots.get(&entry.id).copied());
+        }
+        // Installed, then staged, then shared
+        let states: Vec<_> = entries.iter().map(|e| e.state).collect();
+        assert!(states.windows(2).all(|w| w[0] as u8 <= w[1] as u8));
+    }
+
+    #[test]
+    fn install_completeness() {
+        let mut app = app_from_manifest_str(include_str!("../tests/assets/appmanifest_599140.acf"));
+        // Fully installed with an update pending is still complete
+        assert_eq!(app.install_completeness(), InstallCompleteness::Complete);
+
+        // An interrupted install that's `UpdateRequired | UpdateStarted`
+        app.state_flags = Some(StateFlags(1026));
+        assert_eq!(app.install_completeness(), InstallCompleteness::Partial);
+        app.installed_depots.clear();
+        assert_eq!(app.install_completeness(), InstallCompleteness::Partial);
+        app.bytes_to_download = Some(0);
+        assert_eq!(
+            app.install_completeness(),
+            InstallCompleteness::NotInstalled
+        );
+        app.staged_depots.insert(
+            599_141,
+            Depot {
+                manifest: 1,
+                size: 2,
+                dlc_app_id: None,
+            },
+        );
+        assert_eq!(app.install_completeness(), InstallCompleteness::Partial);
+
+        app.staged_depots.clear();
+        app.state_flags = None;
+        app.bytes_to_download = None;
+        assert_eq!(
+            app.install_completeness(),
+            InstallCompleteness::NotInstalled
+        );
+    }
+
+    #[test]
+    fn comparators() {
+        let gmod = app_from_manifest_str(include_str!("../tests/assets/appmanifest_4000.acf"));
+        let warframe =
+            app_from_manifest_str(include_str!("../tests/assets/appmanifest_230410.acf"));
+        let mut unnamed = gmod.clone();
+        unnamed.app_id = 1;
+        unnamed.name = None;
+        unnamed.size_on_disk = None;
+        unnamed.last_updated = None;
+        let mut lowercase = warframe.clone();
+        lowercase.app_id = 2;
+        lowercase.name = Some("garry's mod".into());
+
+        let mut apps = [unnamed, warframe, lowercase, gmod];
+        let ids = |apps: &[App]| apps.iter().map(|app| app.app_id).collect::<Vec<_>>();
+        apps.sort_by(App::cmp_by_name);
+        assert_eq!(ids(&apps), [4_000, 2, 230_410, 1]);
+        apps.sort_by(App::cmp_by_size);
+        assert_eq!(ids(&apps), [4_000, 2, 230_410, 1]);
+        apps.sort_by(App::cmp_by_last_updated);
+        assert_eq!(ids(&apps), [2, 230_410, 4_000, 1]);
+    }
+
+    #[test]
+    fn pending_update() {
+        let manifest = include_str!("../tests/assets/appmanifest_230410.acf");
+        let mut app = app_from_manifest_str(manifest);
+        app.build_id = Some(100);
+        app.target_build_id = Some(100);
+        app.bytes_to_stage = Some(1_000);
+        app.bytes_staged = Some(250);
+        assert_eq!(app.pending_update(), None);
+        app.target_build_id = Some(0);
+        assert_eq!(app.pending_update(), None);
+        app.target_build_id = None;
+        assert_eq!(app.pending_update(), None);
+
+        app.target_build_id = Some(101);
+        assert_eq!(
+            app.pending_update(),
+            Some(PendingUpdate {
+                current_build_id: Some(100),
+                target_build_id: 101,
+                bytes_to_stage: Some(1_000),
+                bytes_staged: Some(250),
+            })
+        );
+    }
+
+    #[test]
+    fn to_json() {
+        let manifest = include_str!("../tests/assets/appmanifest_230410.acf");
+        let mut app = app_from_manifest_str(manifest);
+        app.name = None;
+        app.install_dir = "Quote \" Slash \\ Tab \t Bell \u{7}".into();
+        app.size_on_disk = None;
+        app.state_flags = Some(StateFlags(6));
+        assert_eq!(
+            app.to_json(),
+            r#"{"app_id":230410,"name":null,"install_dir":"Quote \" Slash \\ Tab \t Bell \u0007","size":null,"state_flags":["UpdateRequired","FullyInstalled"],"installed":true}"#,
+        );
+
+        app.state_flags = None;
+        assert!(app
+            .to_json()
+            .ends_with(r#""state_flags":[],"installed":false}"#));
+    }
+
+    #[test]
+    fn nested_config() {
+        let manifest = include_str!("../tests/assets/appmanifest_2519830_nested_config.acf");
+        let app = app_from_manifest_str(manifest);
+        let expected: BTreeMap<_, _> = [("BetaKey", "beta"), ("language", "english")]
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
+        assert_eq!(app.user_config, expected);
+        assert_eq!(app.beta_branch(), Some("beta"));
+        assert_eq!(app.mounted_config.len(), 1);
+        assert_eq!(app.mounted_config["language"], "english");
+    }
+
+    #[test]
+    fn display_name() {
+        let manifest = include_str!("../tests/assets/appmanifest_4000.acf");
+        let mut app = app_from_manifest_str(manifest);
+        assert_eq!(app.display_name(), "Garry's Mod");
+
+        app.name = None;
+        assert_eq!(app.display_name(), "GarrysMod");
+    }
+
+    #[test]
+    fn content_fingerprint() {
+        let manifest = include_str!("../tests/assets/appmanifest_4000.acf");
+        let app = app_from_manifest_str(manifest);
+
+        // Unrelated to the installed content
+        let mut moved = app.clone();
+        moved.install_dir = "elsewhere".into();
+        moved.last_updated = None;
+        moved.size_on_disk = Some(1);
+        for depot in moved.installed_depots.values_mut() {
+            depot.size = 0;
+        }
+        assert_eq!(app.content_fingerprint(), moved.content_fingerprint());
+
+        let mut rebuilt = app.clone();
+        rebuilt.build_id = rebuilt.build_id.map(|id| id + 1);
+        assert_ne!(app.content_fingerprint(), rebuilt.content_fingerprint());
+
+        let mut new_manifest = app.clone();
+        let depot = new_manifest.installed_depots.values_mut().next().unwrap();
+        depot.manifest += 1;
+        assert_ne!(
+            app.content_fingerprint(),
+            new_manifest.content_fingerprint()
+        );
+    }
+
+    #[test]
+    fn same_identity() {
+        let manifest = include_str!("../tests/assets/appmanifest_599140.acf");
+        let app = app_from_manifest_str(manifest);
+
+        let mut in_progress = app.clone();
+        in_progress.bytes_downloaded = Some(12_345);
+        in_progress.bytes_staged = Some(678);
+        assert!(app.same_identity(&in_progress));
+
+        let mut updated = app.clone();
+        updated.build_id = Some(1);
+        assert!(!app.same_identity(&updated));
+    }
+
+    #[test]
+    fn is_public() {
+        let manifest = r#"
+"AppState"
+{
+	"appid"		"2519830"
+	"installdir" "Resonite"
+	"Universe" "2"
+}
+"#;
+        let app = app_from_manifest_str(manifest);
+        assert_eq!(app.universe, Some(Universe::Beta));
+        assert!(!app.is_public());
+
+        let manifest = include_str!("../tests/assets/appmanifest_230410.acf");
+        let app = app_from_manifest_str(manifest);
+        assert!(app.is_public());
+    }
+
+    #[test]
+    fn is_tool() {
+        let proton = app_from_manifest_str(
+            r#"
+"AppState"
+{
+    "appid"        "1493710"
+    "installdir"        "Proton - Experimental"
+    "name"        "Proton Experimental"
+}
+"#,
+        );
+        assert!(proton.is_tool());
+
+        let runtime = app_from_manifest_str(
+            r#"
+"AppState"
+{
+    "appid"        "9999999"
+    "installdir"        "SteamLinuxRuntime_future"
+    "name"        "Steam Linux Runtime 4.0"
+}
+"#,
+        );
+        assert!(runtime.is_tool());
+
+        let nameless = app_from_manifest_str(
+            r#"
+"AppState"
+{
+    "appid"        "2519830"
+    "installdir" "Resonite"
+}
+"#,
+        );
+        assert!(nameless.is_tool());
+
+        let game = app_from_manifest_str(include_str!("../tests/assets/appmanifest_230410.acf"));
+        assert!(!game.is_tool());
+    }
+
+    #[test]
+    fn is_dedicated_server() {
+        let known = app_from_manifest_str(
+            r#"
+"AppState"
+{
+    "appid"        "232250"
+    "installdir"        "Team Fortress 2 Dedicated Server"
+}
+"#,
+        );
+        assert!(known.is_dedicated_server());
+
+        let by_name = app_from_manifest_str(
+            r#"
+"AppState"
+{
+    "appid"        "9999999"
+    "installdir"        "SomeGameServer"
+    "name"        "Some Game Dedicated server"
+}
+"#,
+        );
+        assert!(by_name.is_dedicated_server());
+
+        let game = app_from_manifest_str(include_str!("../tests/assets/appmanifest_230410.acf"));
+        assert!(!game.is_dedicated_server());
+    }
+
+    #[test]
+    fn state_flags_display() {
+        let mut app = app_from_manifest_str(include_str!("../tests/assets/appmanifest_599140.acf"));
+        assert_eq!(app.state_flags_display(), "UpdateRequired | FullyInstalled");
+
+        app.state_flags = Some(StateFlags(0));
+        assert_eq!(app.state_flags_display(), "Invalid");
+
+        app.state_flags = Some(StateFlags(1 << 13));
+        assert_eq!(app.state_flags_display(), "Unknown(13)");
+
+        app.state_flags = None;
+        assert_eq!(app.state_flags_display(), "<none>");
+    }
+
     #[test]
     fn state_flags() {
         let mut it = StateFlags(0).flags();
@@ -0,0 +1,163 @@
+//! An opt-in caching layer over [`SteamDir`]
+//!
+//! Everything on [`SteamDir`] reads from the filesystem each time that it's called, so it always
+//! reflects the current state of the Steam installation. That's usually what you want, but it can
+//! be wasteful for something like a GUI that repeatedly queries the same information.
+//! [`CachedSteamDir`] trades freshness for speed by memoizing the library listing and any parsed
+//! apps until it's explicitly [invalidated][CachedSteamDir::invalidate].
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::{App, Library, Result, SteamDir};
+
+/// A [`SteamDir`] that memoizes its libraries and apps
+///
+/// Libraries are read the first time that they're needed and apps are parsed the first time that
+/// they're looked up. Both are then served from memory until [`CachedSteamDir::invalidate()`] is
+/// called
+///
+/// # Staleness
+///
+/// Nothing is done to detect changes made by Steam after a value is cached. Apps that get
+/// installed, updated, moved, or uninstalled (along with libraries that get added or removed)
+/// won't be reflected until the cache is invalidated. It's up to you to decide when that's
+/// appropriate e.g. on a timer, when the user hits refresh, or when a filesystem watcher fires.
+/// Prefer using [`SteamDir`] directly if you always need up-to-date information
+///
+/// Errors are never cached, so a failed lookup will be retried on the next call
+///
+/// # Example
+///
+/// ```
+/// # use steamlocate::__private_tests::prelude::*;
+/// # let temp_steam_dir = expect_test_env();
+/// # let steam_dir = temp_steam_dir.steam_dir().clone();
+/// use steamlocate::CachedSteamDir;
+///
+/// # /*
+/// let steam_dir = SteamDir::locate()?;
+/// # */
+/// let mut cached = CachedSteamDir::new(steam_dir);
+/// const GMOD: u32 = 4_000;
+/// // The first lookup reads from disk while later ones are served from the cache
+/// let (gmod, _library) = cached.find_app(GMOD)?.unwrap();
+/// assert_eq!(gmod.app_id, GMOD);
+/// let (gmod, _library) = cached.find_app(GMOD)?.unwrap();
+/// # assert_eq!(gmod.app_id, GMOD);
+///
+/// // Pick up any changes made since then
+/// cached.invalidate();
+/// # Ok::<_, TestError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct CachedSteamDir {
+    steam_dir: SteamDir,
+    libraries: Option<Vec<Library>>,
+    // Maps an app's ID to the app and the index of the library that contains it. `None` caches
+    // that the app wasn't found
+    apps: HashMap<u32, Option<(App, usize)>>,
+}
+
+impl From<SteamDir> for CachedSteamDir {
+    fn from(steam_dir: SteamDir) -> Self {
+        Self::new(steam_dir)
+    }
+}
+
+impl CachedSteamDir {
+    /// Wraps `steam_dir` with an initially empty cache
+    pub fn new(steam_dir: SteamDir) -> Self {
+        Self {
+            steam_dir,
+            libraries: None,
+            apps: HashMap::new(),
+        }
+    }
+
+    /// The underlying (uncached) [`SteamDir`]
+    pub fn steam_dir(&self) -> &SteamDir {
+        &self.steam_dir
+    }
+
+    /// Drops everything that has been cached so far
+    ///
+    /// The next call that needs any information will re-read it from disk
+    pub fn invalidate(&mut self) {
+        self.libraries = None;
+        self.apps.clear();
+    }
+
+    /// Returns all of the [`Library`]s for this installation, reading them if they aren't
+    /// already cached
+    ///
+    /// Unlike [`SteamDir::libraries()`] this fails if any individual library fails to load
+    pub fn libraries(&mut self) -> Result<&[Library]> {
+        let libraries = match self.libraries.take() {
+            Some(libraries) => libraries,
+            None => self.steam_dir.libraries()?.collect::<Result<_>>()?,
+        };
+        Ok(self.libraries.insert(libraries))
+    }
+
+    /// Cached version of [`SteamDir::find_app()`]
+    pub fn find_app(&mut self, app_id: u32) -> Result<Option<(&App, &Library)>> {
+        self.libraries()?;
+        let libraries = self.libraries.as_deref().unwrap_or_default();
+
+        if let Entry::Vacant(entry) = self.apps.entry(app_id) {
+            let found = libraries
+                .iter()
+                .enumerate()
+                .find_map(|(i, library)| {
+                    library
+                        .installed_app(app_id)
+                        .map(|app| app.map(|app| (app, i)))
+                })
+                .transpose()?;
+            entry.insert(found);
+        }
+
+        let found = self.apps.get(&app_id).and_then(Option::as_ref);
+        Ok(found.map(|(app, i)| (app, &libraries[*i])))
+    }
+
+    /// Returns every installed [`App`] along with the [`Library`] that contains it, parsing any
+    /// apps that aren't already cached
+    ///
+    /// Apps are returned in library order
+    pub fn apps(&mut self) -> Result<Vec<(&App, &Library)>> {
+        self.libraries()?;
+        let libraries = self.libraries.as_deref().unwrap_or_default();
+
+        // An app can be listed in one library while it's actually installed in a later one, so
+        // misses are only cached once every library has been checked
+        let mut missing = Vec::new();
+        for (i, library) in libraries.iter().enumerate() {
+            for &app_id in library.app_ids() {
+                if let Entry::Vacant(entry) = self.apps.entry(app_id) {
+                    match library.installed_app(app_id).transpose()? {
+                        Some(app) => {
+                            entry.insert(Some((app, i)));
+                        }
+                        None => missing.push(app_id),
+                    }
+                }
+            }
+        }
+        for app_id in missing {
+            self.apps.entry(app_id).or_insert(None);
+        }
+
+        let apps = libraries
+            .iter()
+            .enumerate()
+            .flat_map(|(i, library)| library.app_ids().iter().map(move |app_id| (i, app_id)))
+            .filter_map(|(i, app_id)| match self.apps.get(app_id) {
+                // The same app can be listed in multiple libraries, but only the first one is used
+                Some(Some((app, app_library))) if *app_library == i => Some((app, &libraries[i])),
+                _ => None,
+            })
+            .collect();
+        Ok(apps)
+    }
+}
@@ -0,0 +1,48 @@
+//! Support for cancelling long-running scans
+//!
+//! _See [`SteamDir::libraries_cancellable()`][crate::SteamDir::libraries_cancellable] and
+//! [`Library::apps_cancellable()`][crate::Library::apps_cancellable]_
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{Error, Result};
+
+/// An [`Iterator`] adaptor that stops once a cancel flag gets set
+///
+/// The flag is checked before each item, so cancelling takes effect between entries. After
+/// cancellation a single [`Error::Cancelled`] is returned followed by [`None`]
+pub struct Cancellable<'cancel, I> {
+    inner: I,
+    cancel: &'cancel AtomicBool,
+    done: bool,
+}
+
+impl<'cancel, I> Cancellable<'cancel, I> {
+    pub(crate) fn new(inner: I, cancel: &'cancel AtomicBool) -> Self {
+        Self {
+            inner,
+            cancel,
+            done: false,
+        }
+    }
+}
+
+impl<I, T> Iterator for Cancellable<'_, I>
+where
+    I: Iterator<Item = Result<T>>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.cancel.load(Ordering::Relaxed) {
+            self.done = true;
+            return Some(Err(Error::Cancelled));
+        }
+
+        self.inner.next()
+    }
+}
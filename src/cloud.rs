@@ -0,0 +1,101 @@
+//! Steam Cloud's inventory of synced files
+//!
+//! _See [`SteamDir::cloud_files()`][crate::SteamDir::cloud_files] for reading the inventory_
+
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    error::{ParseError, ParseErrorKind},
+    Error, Result,
+};
+
+use keyvalues_parser::{Obj, Vdf};
+
+/// A file that Steam Cloud tracks for an app
+///
+/// Read from the `userdata/<USER_ID>/<APP_ID>/remotecache.vdf` files
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CloudFile {
+    /// The account ID of the user that the file belongs to
+    pub user_id: u32,
+    /// The file's path relative to its cloud root
+    pub path: String,
+    /// The file's size in bytes
+    pub size: Option<u64>,
+    /// When the local copy of the file was last modified
+    pub local_time: Option<SystemTime>,
+    /// When the copy of the file stored in Steam Cloud was last modified
+    pub remote_time: Option<SystemTime>,
+    /// Steam's (undocumented) sync state for the file
+    pub sync_state: Option<u32>,
+}
+
+/// Parses all of the files listed in the `remotecache.vdf` at `path`
+///
+/// The file looks like
+///
+/// ```vdf
+/// "<APP_ID>"
+/// {
+///     "ChangeNumber"    "<NUMBER>"
+///     ...
+///     "<FILE_PATH>"
+///     {
+///         "size"    "<BYTES>"
+///         "localtime"    "<UNIX_TIMESTAMP>"
+///         "remotetime"    "<UNIX_TIMESTAMP>"
+///         "syncstate"    "<STATE>"
+///         ...
+///     }
+///     ... // for all tracked files
+/// }
+/// ```
+pub(crate) fn parse_remote_cache(path: &Path, user_id: u32) -> Result<Vec<CloudFile>> {
+    let contents = fs::read_to_string(path).map_err(|io| Error::io(io, path))?;
+    let value = Vdf::parse(&contents)
+        .map_err(|err| {
+            Error::parse(
+                ParseErrorKind::RemoteCache,
+                ParseError::from_parser(err),
+                path,
+            )
+        })?
+        .value;
+    let obj = value.get_obj().ok_or_else(|| {
+        Error::parse(
+            ParseErrorKind::RemoteCache,
+            ParseError::unexpected_structure(),
+            path,
+        )
+    })?;
+
+    // Files are the only entries that are objects. Everything else is metadata for the app
+    let files = obj
+        .iter()
+        .filter_map(|(file_path, values)| {
+            let file = values.first()?.get_obj()?;
+            Some(CloudFile {
+                user_id,
+                path: file_path.to_string(),
+                size: get_num(file, "size"),
+                local_time: get_num(file, "localtime").map(to_time),
+                remote_time: get_num(file, "remotetime").map(to_time),
+                sync_state: get_num(file, "syncstate"),
+            })
+        })
+        .collect();
+    Ok(files)
+}
+
+fn get_num<T: std::str::FromStr>(obj: &Obj<'_>, key: &str) -> Option<T> {
+    obj.get(key)?.first()?.get_str()?.parse().ok()
+}
+
+fn to_time(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
@@ -1,5 +1,18 @@
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    ffi::OsStr,
+    fs,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    error::{ParseError, ParseErrorKind},
+    Error, Result,
+};
+
+use keyvalues_parser::{Obj, Vdf};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
@@ -21,8 +34,263 @@ pub(crate) struct Valve {
 
 #[derive(Deserialize, Debug)]
 pub(crate) struct Steam {
-    #[serde(rename = "CompatToolMapping")]
+    #[serde(rename = "CompatToolMapping", default)]
     pub(crate) mapping: HashMap<u32, CompatTool>,
+    #[serde(rename = "AutoUpdateWindowEnabled")]
+    pub(crate) auto_update_window_enabled: Option<bool>,
+    #[serde(rename = "AutoUpdateWindowStart")]
+    pub(crate) auto_update_window_start: Option<i64>,
+    #[serde(rename = "AutoUpdateWindowEnd")]
+    pub(crate) auto_update_window_end: Option<i64>,
+    #[serde(rename = "Accounts", default)]
+    pub(crate) accounts: HashMap<String, Account>,
+    #[serde(alias = "Language")]
+    pub(crate) language: Option<String>,
+    #[serde(rename = "DownloadThrottleKbps")]
+    pub(crate) download_throttle_kbps: Option<u64>,
+    #[serde(rename = "AllowDownloadsDuringGameplay")]
+    pub(crate) allow_downloads_during_gameplay: Option<bool>,
+    #[serde(rename = "SkinV5")]
+    pub(crate) skin_v5: Option<String>,
+    // Older clients stored it here and it can linger around after an update
+    #[serde(rename = "SkinV4")]
+    pub(crate) skin_v4: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct Account {
+    #[serde(rename = "SteamID")]
+    pub(crate) steam_id: Option<u64>,
+}
+
+impl Steam {
+    pub(crate) fn auto_update_window(&self) -> Option<AutoUpdateWindow> {
+        // Steam uses `-1` to indicate that an hour hasn't been set
+        let to_hour = |hour: i64| u8::try_from(hour).ok().filter(|hour| *hour < 24);
+
+        if self.auto_update_window_enabled.is_none()
+            && self.auto_update_window_start.is_none()
+            && self.auto_update_window_end.is_none()
+        {
+            return None;
+        }
+
+        Some(AutoUpdateWindow {
+            enabled: self.auto_update_window_enabled.unwrap_or_default(),
+            start_hour: self.auto_update_window_start.and_then(to_hour),
+            end_hour: self.auto_update_window_end.and_then(to_hour),
+        })
+    }
+
+    pub(crate) fn bandwidth_settings(&self) -> BandwidthSettings {
+        BandwidthSettings {
+            // Steam uses `0` to indicate that downloads aren't limited
+            download_throttle_kbps: self.download_throttle_kbps.filter(|&kbps| kbps != 0),
+            allow_downloads_during_gameplay: self
+                .allow_downloads_during_gameplay
+                .unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn active_skin(self) -> Option<String> {
+        // An empty skin name means that the default skin is used
+        [self.skin_v5, self.skin_v4]
+            .into_iter()
+            .flatten()
+            .find(|skin| !skin.is_empty())
+    }
+}
+
+/// The accounts listed in `config/loginusers.vdf` keyed by their SteamID64
+pub(crate) type LoginUsers = BTreeMap<u64, LoginUser>;
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct LoginUser {
+    // Older versions of Steam wrote this key in all lowercase
+    #[serde(rename = "MostRecent", alias = "mostrecent", default)]
+    pub(crate) most_recent: bool,
+}
+
+/// Parses the account ID from the name of a `userdata/<ACCOUNT_ID>` directory
+///
+/// Steam also keeps some special entries in there like the anonymous `0` user and `ac`, so only
+/// names that are plain, non-zero account IDs are accepted
+pub(crate) fn user_data_account_id(name: &OsStr) -> Option<u32> {
+    let name = name.to_str()?;
+    // `parse()` would also accept things like `+1`
+    if !name.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    name.parse().ok().filter(|&account_id| account_id != 0)
+}
+
+/// Lists the IDs of all the apps that have an entry in the user's `localconfig.vdf` at `path`
+///
+/// The file is huge and loosely structured, so it's walked directly instead of through serde.
+/// Steam isn't consistent with the casing of the keys, so they're matched case-insensitively
+///
+/// ```vdf
+/// "UserLocalConfigStore"
+/// {
+///     "Software"
+///     {
+///         "Valve"
+///         {
+///             "Steam"
+///             {
+///                 "apps"
+///                 {
+///                     "<APP_ID>"
+///                     {
+///                         ...
+///                     }
+///                     ... // for all configured apps
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub(crate) fn local_config_app_ids(path: &Path) -> Result<BTreeSet<u32>> {
+    with_local_config_apps(path, |apps| {
+        apps.into_iter()
+            .flat_map(|apps| apps.keys())
+            .filter_map(|app_id| app_id.parse().ok())
+            .collect()
+    })
+}
+
+/// Reads the `LaunchOptions` that the user set for `app_id` from the `localconfig.vdf` at `path`
+///
+/// Returns [`None`] when the app has no launch options or they're empty
+pub(crate) fn local_config_launch_options(path: &Path, app_id: u32) -> Result<Option<String>> {
+    with_local_config_apps(path, |apps| {
+        let app = get_obj_ignore_case(apps?, &app_id.to_string())?;
+        app.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("LaunchOptions"))
+            .and_then(|(_, values)| values.first())
+            .and_then(|value| value.get_str())
+            .filter(|launch_options| !launch_options.is_empty())
+            .map(ToOwned::to_owned)
+    })
+}
+
+/// Parses the `localconfig.vdf` at `path` and calls `f` with its `apps` section (if any)
+fn with_local_config_apps<T>(path: &Path, f: impl FnOnce(Option<&Obj<'_>>) -> T) -> Result<T> {
+    let contents = fs::read_to_string(path).map_err(|io| Error::io(io, path))?;
+    let vdf = Vdf::parse(&contents).map_err(|err| {
+        Error::parse(
+            ParseErrorKind::LocalConfig,
+            ParseError::from_parser(err),
+            path,
+        )
+    })?;
+
+    let Some(root) = vdf.value.get_obj() else {
+        return Err(Error::parse(
+            ParseErrorKind::LocalConfig,
+            ParseError::unexpected_structure(),
+            path,
+        ));
+    };
+    // Users that never configured any apps won't have the section at all
+    let apps = ["Software", "Valve", "Steam", "apps"]
+        .iter()
+        .try_fold(root, |obj, key| get_obj_ignore_case(obj, key));
+    Ok(f(apps))
+}
+
+fn get_obj_ignore_case<'obj>(obj: &'obj Obj<'_>, key: &str) -> Option<&'obj Obj<'obj>> {
+    obj.iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .and_then(|(_, values)| values.first())
+        .and_then(|value| value.get_obj())
+}
+
+/// The parts of a `package/steam_client_<PLATFORM>.manifest` file that we care about
+#[derive(Deserialize, Debug)]
+pub(crate) struct ClientManifest {
+    pub(crate) version: Option<String>,
+}
+
+/// Steam's global setting for restricting automatic updates to certain hours of the day
+///
+/// Configured under _Settings > Downloads > Schedule auto-updates_ in Steam. Hours are in the
+/// local time of the machine running Steam
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AutoUpdateWindow {
+    /// Whether automatic updates are restricted to the window at all
+    pub enabled: bool,
+    /// The hour of the day (`0..=23`) that the window opens, if set
+    pub start_hour: Option<u8>,
+    /// The hour of the day (`0..=23`) that the window closes, if set
+    ///
+    /// This can be less than [`start_hour`][Self::start_hour] when the window spans midnight
+    pub end_hour: Option<u8>,
+}
+
+impl AutoUpdateWindow {
+    /// The earliest time at or after `time` that falls within the window
+    ///
+    /// `utc_offset_secs` is the offset of the machine's local time from UTC (e.g. `-5 * 60 * 60`
+    /// for UTC-5) since the window's hours are in local time. `time` is returned as-is when the
+    /// window isn't [`enabled`][Self::enabled], is missing either hour, or opens and closes at the
+    /// same hour. Otherwise a `time` outside of the window is moved to the next time it opens
+    pub fn next_allowed(&self, time: SystemTime, utc_offset_secs: i32) -> SystemTime {
+        const HOUR: i64 = 60 * 60;
+        const DAY: i64 = 24 * HOUR;
+
+        let (Some(start), Some(end)) = (self.start_hour, self.end_hour) else {
+            return time;
+        };
+        if !self.enabled || start == end {
+            return time;
+        }
+
+        let Ok(since_epoch) = time.duration_since(SystemTime::UNIX_EPOCH) else {
+            return time;
+        };
+        let Ok(secs) = i64::try_from(since_epoch.as_secs()) else {
+            return time;
+        };
+        let local = secs + i64::from(utc_offset_secs);
+        let (start, end) = (i64::from(start), i64::from(end));
+        let hour = local.rem_euclid(DAY) / HOUR;
+        let in_window = if start < end {
+            (start..end).contains(&hour)
+        } else {
+            // The window spans midnight
+            hour >= start || hour < end
+        };
+        if in_window {
+            return time;
+        }
+
+        let mut opens = local - local.rem_euclid(DAY) + start * HOUR;
+        if opens <= local {
+            opens += DAY;
+        }
+        let delay = u64::try_from(opens - local).unwrap_or_default();
+        // Keep any sub-second part out of the result, so that it lands exactly on the hour
+        time + Duration::from_secs(delay) - Duration::from_nanos(since_epoch.subsec_nanos().into())
+    }
+}
+
+/// Steam's global download bandwidth settings
+///
+/// Configured under _Settings > Downloads_ in Steam. Settings that were never changed from
+/// Steam's defaults are usually missing from `config.vdf`, so those are filled in with the
+/// defaults
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BandwidthSettings {
+    /// The download speed limit in kilobits per second or [`None`] when downloads are unlimited
+    pub download_throttle_kbps: Option<u64>,
+    /// Whether downloads keep running while a game is being played
+    ///
+    /// Steam pauses downloads during gameplay by default
+    pub allow_downloads_during_gameplay: bool,
 }
 
 /// An instance of a compatibility tool.
@@ -33,9 +301,39 @@ pub struct CompatTool {
     /// Example: `proton_411`
     pub name: Option<String>,
 
-    // Unknown option, may be used in the future
+    /// Extra configuration passed along to the tool for this app
+    ///
+    /// This is usually an empty string. Prefer [`CompatTool::config()`] which treats an empty
+    /// config the same as a missing one
     pub config: Option<String>,
 
-    // Unknown option, may be used in the future
+    /// How strongly this mapping applies when multiple mappings could be used
+    ///
+    /// Higher values win. Mappings picked by the user in an app's properties typically use `250`
+    /// while the global default (listed under an app ID of `0`) uses a lower value like `75`
     pub priority: Option<u64>,
 }
+
+impl CompatTool {
+    /// The tool's [`config`][Self::config] if it's set to anything
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for (app_id, tool) in steam_dir.compat_tool_mapping()? {
+    ///     if let Some(config) = tool.config() {
+    ///         println!("{app_id} runs {:?} with {config:?}", tool.name);
+    ///     }
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn config(&self) -> Option<&str> {
+        self.config.as_deref().filter(|config| !config.is_empty())
+    }
+}
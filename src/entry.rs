@@ -0,0 +1,64 @@
+//! A single view over both Steam apps and non-Steam shortcuts
+//!
+//! _See [`SteamDir::all_entries()`][crate::SteamDir::all_entries] for listing every entry_
+
+use crate::{App, Library, Shortcut};
+
+/// Either an installed Steam [`App`] or a non-Steam [`Shortcut`]
+///
+/// Useful for launchers that want to show everything that Steam can launch in a single list
+#[derive(Clone, Debug)]
+// Entries are handed out one at a time, so boxing the app isn't worth the clunkier matching
+#[allow(clippy::large_enum_variant)]
+pub enum Entry {
+    /// An installed Steam app along with the library that contains it
+    App(App, Library),
+    /// A non-Steam game that was added to Steam
+    Shortcut(Shortcut),
+}
+
+impl Entry {
+    /// The game ID that Steam uses to launch the entry
+    ///
+    /// This is the [`app_id`][App::app_id] for apps and [`Shortcut::rungameid()`] for shortcuts.
+    /// The two can never collide since a shortcut's game ID always has its low 32 bits set to
+    /// `0x02000000`
+    pub fn id(&self) -> u64 {
+        match self {
+            Self::App(app, _) => app.app_id.into(),
+            Self::Shortcut(shortcut) => shortcut.rungameid(),
+        }
+    }
+
+    /// The name to show for the entry
+    ///
+    /// This is [`App::display_name()`] for apps and the [`app_name`][Shortcut::app_name] for
+    /// shortcuts
+    pub fn name(&self) -> &str {
+        match self {
+            Self::App(app, _) => app.display_name(),
+            Self::Shortcut(shortcut) => &shortcut.app_name,
+        }
+    }
+
+    /// The `steam://rungameid/<GAME_ID>` URL that launches the entry through Steam
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// let entry = steamlocate::Entry::App(gmod, library);
+    /// assert_eq!(entry.launch_url(), "steam://rungameid/4000");
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn launch_url(&self) -> String {
+        format!("steam://rungameid/{}", self.id())
+    }
+}
@@ -1,17 +1,19 @@
 use std::{
     fmt, io,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[non_exhaustive]
 pub enum Error {
     FailedLocate(LocateError),
     InvalidSteamDir(ValidationError),
     Io {
-        inner: io::Error,
+        // `Arc`ed so that the error can be `Clone`
+        inner: Arc<io::Error>,
         path: PathBuf,
     },
     Parse {
@@ -22,6 +24,16 @@ pub enum Error {
     MissingExpectedApp {
         app_id: u32,
     },
+    /// A directory couldn't be read because of insufficient permissions
+    ///
+    /// This is kept separate from [`Error::Io`] so that callers can easily skip over e.g. a
+    /// library folder that's owned by another user while still reading the rest
+    PermissionDenied {
+        inner: Arc<io::Error>,
+        path: PathBuf,
+    },
+    /// A scan was cancelled through its cancel flag
+    Cancelled,
 }
 
 impl fmt::Display for Error {
@@ -36,16 +48,20 @@ impl fmt::Display for Error {
             Self::Io { inner: err, path } => {
                 write!(f, "Encountered an I/O error: {} at {}", err, path.display())
             }
-            Self::Parse { kind, error, path } => write!(
-                f,
-                "Failed parsing VDF file. File kind: {:?}, Error: {} at {}",
-                kind,
-                error,
-                path.display(),
-            ),
+            Self::Parse { kind, error, path } => {
+                write!(f, "Failed parsing VDF file. File kind: {:?}", kind)?;
+                if let Some(label) = error.label() {
+                    write!(f, " ({})", label)?;
+                }
+                write!(f, ", Error: {} at {}", error, path.display())
+            }
             Self::MissingExpectedApp { app_id } => {
                 write!(f, "Missing expected app with id: {}", app_id)
             }
+            Self::PermissionDenied { inner: err, path } => {
+                write!(f, "Permission denied: {} at {}", err, path.display())
+            }
+            Self::Cancelled => f.write_str("The operation was cancelled"),
         }
     }
 }
@@ -63,11 +79,23 @@ impl Error {
 
     pub(crate) fn io(io: io::Error, path: &Path) -> Self {
         Self::Io {
-            inner: io,
+            inner: Arc::new(io),
             path: path.to_owned(),
         }
     }
 
+    /// Like [`Error::io()`], but permission errors get mapped to [`Error::PermissionDenied`]
+    pub(crate) fn read_dir(io: io::Error, path: &Path) -> Self {
+        if io.kind() == io::ErrorKind::PermissionDenied {
+            Self::PermissionDenied {
+                inner: Arc::new(io),
+                path: path.to_owned(),
+            }
+        } else {
+            Self::io(io, path)
+        }
+    }
+
     pub(crate) fn parse(kind: ParseErrorKind, error: ParseError, path: &Path) -> Self {
         Self::Parse {
             kind,
@@ -80,6 +108,11 @@ impl Error {
 #[derive(Clone, Debug)]
 pub enum LocateError {
     Backend(BackendError),
+    /// None of the places that Steam gets installed to exist, so Steam likely isn't installed
+    ///
+    /// A location that exists, but isn't a valid installation is reported as
+    /// [`Error::InvalidSteamDir`] instead
+    NotFound,
     Unsupported,
 }
 
@@ -103,6 +136,7 @@ impl fmt::Display for LocateError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Backend(error) => error.fmt(f),
+            Self::NotFound => f.write_str("No Steam installation was found"),
             Self::Unsupported => f.write_str("Unsupported platform"),
         }
     }
@@ -159,14 +193,25 @@ impl ValidationError {
             inner: ValidationErrorInner::MissingDirectory,
         }
     }
+
+    pub(crate) fn foreign_path(path: &Path) -> Self {
+        Self {
+            inner: ValidationErrorInner::ForeignPath(path.to_owned()),
+        }
+    }
 }
 
 impl fmt::Display for ValidationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.inner {
+        match &self.inner {
             ValidationErrorInner::MissingDirectory => f.write_str(
                 "The Steam installation directory either isn't a directory or doesn't exist",
             ),
+            ValidationErrorInner::ForeignPath(path) => write!(
+                f,
+                "Library path appears to be from a different OS: {}",
+                path.display(),
+            ),
         }
     }
 }
@@ -174,6 +219,7 @@ impl fmt::Display for ValidationError {
 #[derive(Clone, Debug)]
 enum ValidationErrorInner {
     MissingDirectory,
+    ForeignPath(PathBuf),
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -183,14 +229,24 @@ pub enum ParseErrorKind {
     LibraryFolders,
     App,
     Shortcut,
+    LoginUsers,
+    ClientManifest,
+    RemoteCache,
+    LocalConfig,
+    /// A file parsed through [`vdf::parse_file()`][crate::vdf::parse_file]
+    ///
+    /// The label that was passed in is available from [`ParseError::label()`]
+    Custom,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ParseError {
     // Keep `keyvalues_parser` and `keyvalues_serde` types out of the public API (this includes
-    // from traits, so no using `thiserror` with `#[from]`)
+    // from traits, so no using `thiserror` with `#[from]`). The inner errors aren't `Clone`, so
+    // they're shared through an `Arc` instead
     #[allow(dead_code)] // Only used for displaying currently
-    inner: Box<ParseErrorInner>,
+    inner: Arc<ParseErrorInner>,
+    label: Option<Arc<str>>,
 }
 
 impl fmt::Display for ParseError {
@@ -221,16 +277,34 @@ impl fmt::Display for ParseErrorInner {
 impl ParseError {
     pub(crate) fn new(inner: ParseErrorInner) -> Self {
         Self {
-            inner: Box::new(inner),
+            inner: Arc::new(inner),
+            label: None,
         }
     }
 
+    pub(crate) fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// The label identifying the file for errors of [`ParseErrorKind::Custom`]
+    ///
+    /// This is the `kind_label` that was passed to [`vdf::parse_file()`][crate::vdf::parse_file]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
     pub(crate) fn from_parser(err: keyvalues_parser::error::Error) -> Self {
         Self::new(ParseErrorInner::Parse(err))
     }
 
     pub(crate) fn from_serde(err: keyvalues_serde::error::Error) -> Self {
-        Self::new(ParseErrorInner::Serde(err))
+        match err {
+            // The serde error only displays a generic message for these while the underlying
+            // parser error includes the position of the syntax error within the file
+            keyvalues_serde::error::Error::Parse(parse_err) => Self::from_parser(parse_err),
+            err => Self::new(ParseErrorInner::Serde(err)),
+        }
     }
 
     pub(crate) fn unexpected_structure() -> Self {
@@ -241,3 +315,60 @@ impl ParseError {
         Self::new(ParseErrorInner::Missing)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn serde_syntax_error_keeps_position() {
+        let malformed = "\"InstallConfigStore\"\n{\n\t\"Software\"\n\t{\n";
+        let parser_err = keyvalues_parser::Vdf::parse(malformed).unwrap_err();
+        let serde_err =
+            keyvalues_serde::from_str::<BTreeMap<String, String>>(malformed).unwrap_err();
+
+        let err = ParseError::from_serde(serde_err);
+        assert_eq!(err.to_string(), parser_err.to_string());
+    }
+
+    #[test]
+    fn clone() {
+        let io_err = io::Error::new(io::ErrorKind::NotFound, "gone");
+        let err = Error::io(io_err, Path::new("some/file"));
+        assert_eq!(err.clone().to_string(), err.to_string());
+
+        let err = Error::parse(
+            ParseErrorKind::App,
+            ParseError::unexpected_structure(),
+            Path::new("some/manifest.acf"),
+        );
+        assert_eq!(err.clone().to_string(), err.to_string());
+    }
+
+    #[test]
+    fn locate_not_found() {
+        let err = Error::locate(LocateError::NotFound);
+        assert!(matches!(err, Error::FailedLocate(LocateError::NotFound)));
+        assert_eq!(
+            err.to_string(),
+            "Failed locating the steam dir. Error: No Steam installation was found",
+        );
+    }
+
+    #[test]
+    fn read_dir_permission_denied() {
+        let path = Path::new("other/user/library/steamapps");
+        let io_err = io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        let err = Error::read_dir(io_err, path);
+        assert!(
+            matches!(&err, Error::PermissionDenied { path: p, .. } if p == path),
+            "{err:?}"
+        );
+
+        let io_err = io::Error::new(io::ErrorKind::NotFound, "gone");
+        let err = Error::read_dir(io_err, path);
+        assert!(matches!(err, Error::Io { .. }), "{err:?}");
+    }
+}
@@ -0,0 +1,56 @@
+//! Small formatting helpers for displaying data parsed by this crate
+
+/// Formats a byte count as a human readable string using binary (IEC) units
+///
+/// Sizes are divided by powers of 1,024 and labeled accordingly (`KiB`, `MiB`, `GiB`, ...) so that
+/// the unit always matches the math. Anything below 1 KiB is displayed as a whole number of bytes
+/// while larger values are displayed with one decimal place.
+///
+/// # Example
+///
+/// ```
+/// use steamlocate::fmt::human_bytes;
+///
+/// assert_eq!(human_bytes(512), "512 B");
+/// assert_eq!(human_bytes(1_536), "1.5 KiB");
+/// assert_eq!(human_bytes(4_152_333_499), "3.9 GiB");
+/// ```
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    if bytes < 1_024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1_024.0;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        // Compare after rounding to the displayed precision, so that e.g. 1,023.95 KiB shows up as
+        // 1.0 MiB instead of 1024.0 KiB
+        if (size * 10.0).round() < 10_240.0 {
+            break;
+        }
+        size /= 1_024.0;
+        unit = next_unit;
+    }
+
+    format!("{size:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_boundaries() {
+        assert_eq!(human_bytes(0), "0 B");
+        assert_eq!(human_bytes(1_023), "1023 B");
+        assert_eq!(human_bytes(1_024), "1.0 KiB");
+        assert_eq!(human_bytes(1_024 * 1_024), "1.0 MiB");
+        // Just under a boundary rounds up into the next unit
+        assert_eq!(human_bytes(1_048_575), "1.0 MiB");
+        assert_eq!(human_bytes(1_048_473), "1023.9 KiB");
+        assert_eq!(human_bytes(29_070_834_580), "27.1 GiB");
+        assert_eq!(human_bytes(u64::MAX), "16.0 EiB");
+    }
+}
@@ -82,6 +82,25 @@
 //!     App 1714040 - Super Auto Pets
 //!     App 2348590 - Proton 8.0
 //! ```
+//!
+//! # Thread safety
+//!
+//! Every public type in this crate is both [`Send`] and [`Sync`]. [`SteamDir`], [`Library`], and
+//! [`App`] are plain data (paths, IDs, and parsed values) that can be cloned or shared behind an
+//! [`Arc`][std::sync::Arc] freely, and [`Error`] shares its inner I/O and parse errors through an
+//! `Arc` so it can be sent along too.
+//!
+//! The iterators ([`library::Iter`], [`shortcut::Iter`], etc.) are also `Send` and `Sync`, so they
+//! can be moved to a worker thread or held across an `.await`. They read from the filesystem
+//! lazily though, so calling `next()` blocks the current thread on I/O. In an async runtime drive
+//! them from a blocking task (e.g. `tokio::task::spawn_blocking()`) instead.
+//!
+//! Directories are listed eagerly and files are closed as soon as they've been read, so neither the
+//! types nor the iterators hold onto file descriptors between calls. Keeping many [`Library`]s or
+//! iterators around at once won't run into the process's open file limit (e.g. `ulimit -n`).
+//!
+//! None of the types lock any files, so Steam can change the data on disk at any time. Re-read
+//! anything that needs to be up-to-date rather than relying on a value that was read earlier.
 
 #![warn(
 	// We're a library after all
@@ -91,29 +110,51 @@
 )]
 
 pub mod app;
+pub mod cached;
+pub mod cancel;
+pub mod cloud;
 pub mod config;
+pub mod entry;
 pub mod error;
+pub mod fmt;
 pub mod library;
 mod locate;
+pub mod prelude;
+pub mod reveal;
+pub mod scan;
 pub mod shortcut;
+pub mod snapshot;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod trace;
+pub mod validation;
+pub mod vdf;
 // NOTE: exposed publicly, so that we can use them in doctests
 /// Not part of the public API >:V
 #[doc(hidden)]
 pub mod __private_tests;
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
 
 use error::ValidationError;
 
 use crate::error::{ParseError, ParseErrorKind};
 
 pub use crate::app::App;
-pub use crate::config::CompatTool;
+pub use crate::cached::CachedSteamDir;
+pub use crate::config::{AutoUpdateWindow, BandwidthSettings, CompatTool};
+pub use crate::entry::Entry;
 pub use crate::error::{Error, Result};
 pub use crate::library::Library;
+pub use crate::locate::InstallationType;
+pub use crate::scan::ScanResult;
 pub use crate::shortcut::Shortcut;
+pub use crate::snapshot::SteamSnapshot;
+pub use crate::validation::ValidationReport;
 
 // Run doctests on the README too
 #[doc = include_str!("../README.md")]
@@ -132,10 +173,19 @@ pub struct ReadmeDoctests;
 ///   - [`steam_dir.libraries()`][SteamDir::libraries]
 /// - Convenient access to find a specific app by id
 ///   - [`steam_dir.find_app(app_id)`][SteamDir::find_app]
+/// - Every installed app across all libraries, optionally skipping tools
+///   - [`steam_dir.apps()`][SteamDir::apps] and [`steam_dir.games()`][SteamDir::games]
 /// - Compatibility tool mapping (aka Proton to game mapping)
 ///   - [`steam_dir.compat_tool_mapping()`][SteamDir::compat_tool_mapping]
+/// - Global settings from Steam's config
+///   - [`steam_dir.auto_update_window()`][SteamDir::auto_update_window]
+///   - [`steam_dir.language()`][SteamDir::language]
+/// - The most recently logged in user
+///   - [`steam_dir.most_recent_user()`][SteamDir::most_recent_user]
 /// - Shortcuts info (aka the listing of non-Steam games)
 ///   - [`steam_dir.shortcuts()`][SteamDir::shortcuts]
+/// - A health check of the whole installation
+///   - [`steam_dir.validate()`][SteamDir::validate]
 ///
 /// # Example
 /// ```
@@ -160,6 +210,22 @@ impl SteamDir {
     /// platforms are Windows, MacOS, and Linux while other platforms return an
     /// [`LocateError::Unsupported`][error::LocateError::Unsupported]
     ///
+    /// Before falling back to the platform specific lookup, the environment variables used by
+    /// SteamCMD are consulted in the following order of precedence:
+    ///
+    /// 1. `STEAM_BASE_FOLDER`
+    /// 2. `STEAM_ROOT`
+    ///
+    /// A variable is only used when it points to an existing directory, otherwise the next one is
+    /// tried instead
+    ///
+    /// # Errors
+    ///
+    /// - [`LocateError::NotFound`][error::LocateError::NotFound] (wrapped in
+    ///   [`Error::FailedLocate`]) when Steam doesn't appear to be installed at all
+    /// - [`Error::InvalidSteamDir`] when an installation was found, but it isn't valid (e.g. the
+    ///   registry points to a directory that was deleted)
+    ///
     /// [See the struct docs][Self#example] for an example
     pub fn locate() -> Result<Self> {
         let path = locate::locate_steam_dir()?;
@@ -167,10 +233,51 @@ impl SteamDir {
         Self::from_dir(&path)
     }
 
+    /// Locates every Steam installation on this computer along with how each was installed
+    ///
+    /// This consults the same places as [`SteamDir::locate()`] in the same order of precedence,
+    /// but keeps going after the first hit. Installations that are reachable through multiple
+    /// paths (e.g. Linux's `~/.steam/steam` symlink) are only listed once with the type of the
+    /// first path that found them. On Windows and macOS there is at most one platform specific
+    /// installation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::SteamDir;
+    /// # /*
+    /// for (steam_dir, installation_type) in SteamDir::locate_all_with_types()? {
+    ///     println!("{installation_type:?} - {}", steam_dir.path().display());
+    /// }
+    /// # */
+    /// # Ok::<_, steamlocate::Error>(())
+    /// ```
+    pub fn locate_all_with_types() -> Result<Vec<(Self, InstallationType)>> {
+        let mut steam_dirs: Vec<(Self, InstallationType)> = Vec::new();
+        for (path, installation_type) in locate::locate_steam_dirs()? {
+            let Ok(steam_dir) = Self::from_dir(&path) else {
+                continue;
+            };
+            if !steam_dirs
+                .iter()
+                .any(|(seen, _)| seen.path == steam_dir.path)
+            {
+                steam_dirs.push((steam_dir, installation_type));
+            }
+        }
+
+        Ok(steam_dirs)
+    }
+
     /// Attempt to create a [`SteamDir`] from its installation directory
     ///
     /// When possible you should prefer using [`SteamDir::locate()`]
     ///
+    /// The provided `path` is canonicalized up front (following any symlinks like Linux's
+    /// `~/.steam/root`), so [`SteamDir::path()`] always returns the canonical path regardless of
+    /// which link was passed in. On Windows the path isn't canonicalized, and any verbatim
+    /// (`\\?\`) prefix is stripped when the path can be represented without it
+    ///
     /// # Example
     ///
     /// ```
@@ -190,29 +297,260 @@ impl SteamDir {
             return Err(Error::validation(ValidationError::missing_dir()));
         }
 
+        let path = if cfg!(windows) {
+            library::normalize_path(path.to_owned())
+        } else {
+            fs::canonicalize(path).map_err(|io| Error::io(io, path))?
+        };
+
         // TODO(cosmic): should we do some kind of extra validation here? Could also use validation
         // to determine if a steam dir has been uninstalled. Should fix all the flatpack/snap issues
-        Ok(Self {
-            path: path.to_owned(),
-        })
+        Ok(Self { path })
     }
 
     /// The path to the Steam installation directory on this computer.
     ///
     /// Example: `C:\Program Files (x86)\Steam`
+    ///
+    /// On Windows this path, along with the library paths, never includes the verbatim (`\\?\`)
+    /// prefix unless the path can't be represented without it
     pub fn path(&self) -> &Path {
         &self.path
     }
 
+    /// The Steam installation's `config` directory
+    ///
+    /// This holds the global configuration like `config.vdf` and `loginusers.vdf`. The path is
+    /// returned whether or not it exists. Note that `config.vdf` is read from
+    /// `steam/config/config.vdf` instead on installs that only have it there
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let login_users = steam_dir.config_dir().join("loginusers.vdf");
+    /// println!("Accounts are listed in {}", login_users.display());
+    /// # assert!(login_users.starts_with(steam_dir.path()));
+    /// # std::fs::create_dir_all(steam_dir.config_dir())?;
+    /// # assert_eq!(steam_dir.config_dir_checked(), Some(steam_dir.config_dir()));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn config_dir(&self) -> PathBuf {
+        self.path.join("config")
+    }
+
+    /// Like [`SteamDir::config_dir()`], but returns [`None`] if the directory doesn't exist
+    pub fn config_dir_checked(&self) -> Option<PathBuf> {
+        Some(self.config_dir()).filter(|dir| dir.is_dir())
+    }
+
+    /// The Steam installation's `userdata` directory
+    ///
+    /// This holds a `userdata/<USER_ID>` directory for each account that has logged in on this
+    /// computer. The path is returned whether or not it exists
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// if let Some(user_data) = steam_dir.userdata_dir_checked() {
+    ///     for entry in std::fs::read_dir(user_data)? {
+    ///         println!("Found user data for {:?}", entry?.file_name());
+    ///     }
+    /// }
+    /// # assert!(steam_dir.userdata_dir().ends_with("userdata"));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn userdata_dir(&self) -> PathBuf {
+        self.path.join("userdata")
+    }
+
+    /// Like [`SteamDir::userdata_dir()`], but returns [`None`] if the directory doesn't exist
+    pub fn userdata_dir_checked(&self) -> Option<PathBuf> {
+        Some(self.userdata_dir()).filter(|dir| dir.is_dir())
+    }
+
+    /// Builds a command that reveals the Steam installation directory in a file manager
+    ///
+    /// _See [`reveal::reveal_command()`] for more info_
+    pub fn reveal_command(&self) -> Command {
+        reveal::reveal_command(&self.path)
+    }
+
     pub fn library_paths(&self) -> Result<Vec<PathBuf>> {
+        let folders = self.library_folders()?;
+        Ok(folders.into_iter().map(|folder| folder.path).collect())
+    }
+
+    /// Registers `libraries` in Steam's `steamapps/libraryfolders.vdf`
+    ///
+    /// Libraries that are already listed (matched by path) have their list of apps updated while
+    /// new ones are appended. Nothing is ever removed, and all of the existing entries' other
+    /// information is kept intact, although keys may be reordered. The file is replaced in one go
+    /// by renaming a temporary file over it, so a failed write leaves the original untouched
+    ///
+    /// Steam rewrites this file while it's running, so make sure that Steam is closed first or
+    /// your changes may be overwritten
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # let library = steam_dir.libraries()?.next().unwrap()?;
+    /// # let new_path = steam_dir.path().parent().unwrap().join("NewLibrary");
+    /// # fs::create_dir_all(new_path.join("steamapps"))?;
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// let library = /* Somehow get a library */;
+    /// let new_path = /* Somewhere to move it to */;
+    /// # */
+    /// let moved = library.with_path(new_path);
+    /// steam_dir.write_library_paths(&[moved])?;
+    /// # assert_eq!(steam_dir.library_count()?, 3);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn write_library_paths(&self, libraries: &[Library]) -> Result<()> {
         let libraryfolders_vdf = self.path.join("steamapps").join("libraryfolders.vdf");
-        library::parse_library_paths(&libraryfolders_vdf)
+        library::write_library_folders(&libraryfolders_vdf, libraries)
+    }
+
+    fn library_folders(&self) -> Result<Vec<library::LibraryFolder>> {
+        let libraryfolders_vdf = self.path.join("steamapps").join("libraryfolders.vdf");
+        library::parse_library_folders(&libraryfolders_vdf)
+    }
+
+    /// Takes an owned, serializable [`SteamSnapshot`] of the installation and its libraries
+    ///
+    /// When `include_apps` is set each library's apps are parsed and included as well
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let snapshot = steam_dir.snapshot(true)?;
+    /// for library in &snapshot.libraries {
+    ///     for app in library.apps.iter().flatten() {
+    ///         println!("{} - {:?}", app.app_id, app.name);
+    ///     }
+    /// }
+    /// // ... then serialize it with your serde data format of choice
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn snapshot(&self, include_apps: bool) -> Result<SteamSnapshot> {
+        use snapshot::{AppSnapshot, LibrarySnapshot};
+
+        let libraries = self
+            .libraries()?
+            .map(|library| {
+                let library = library?;
+                let apps = if include_apps {
+                    let apps = library
+                        .app_ids()
+                        .iter()
+                        .filter_map(|&app_id| library.installed_app(app_id))
+                        .map(|app| app.map(|app| AppSnapshot::from(&app)))
+                        .collect::<Result<_>>()?;
+                    Some(apps)
+                } else {
+                    None
+                };
+                Ok(LibrarySnapshot::new(&library, apps))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(SteamSnapshot::new(self.path.clone(), libraries))
+    }
+
+    /// Runs a health check over the installation, reporting the outcome of each check
+    ///
+    /// This checks that
+    ///
+    /// - The `steamapps` directory is readable
+    /// - `steamapps/libraryfolders.vdf` can be parsed
+    /// - Each library that it lists can be read
+    /// - `config/config.vdf` can be parsed
+    /// - The `userdata` directory is readable
+    ///
+    /// Every check is run regardless of whether an earlier one failed (except for the libraries
+    /// which require `libraryfolders.vdf`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// let report = steam_dir.validate();
+    /// print!("{report}");
+    /// if !report.is_healthy() {
+    ///     println!("Something is wrong with your Steam installation");
+    /// }
+    /// # assert!(report.is_healthy());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn validate(&self) -> ValidationReport {
+        use validation::Check;
+
+        let steamapps = self.path.join("steamapps");
+        let steamapps_dir = Check::new(
+            &steamapps,
+            fs::read_dir(&steamapps).map_err(|io| Error::io(io, &steamapps)),
+        );
+
+        let library_paths = self.library_paths();
+        let libraries = match &library_paths {
+            Ok(paths) => paths
+                .iter()
+                .map(|path| Check::new(path, Library::from_dir(path)))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        let library_folders = Check::new(&steamapps.join("libraryfolders.vdf"), library_paths);
+
+        let config = Check::new(&self.config_vdf_path(), self.read_config());
+
+        let userdata = self.userdata_dir();
+        let userdata_dir = Check::new(
+            &userdata,
+            fs::read_dir(&userdata).map_err(|io| Error::io(io, &userdata)),
+        );
+
+        ValidationReport {
+            steamapps_dir,
+            library_folders,
+            libraries,
+            config,
+            userdata_dir,
+        }
     }
 
     /// Returns an [`Iterator`] over all the [`Library`]s believed to be part of this installation
     ///
     /// For reasons akin to [`std::fs::read_dir()`] this method both returns a [`Result`] and
-    /// returns [`Result`]s for the iterator's items.
+    /// returns [`Result`]s for the iterator's items. A library that fails to load doesn't stop the
+    /// iteration, so you can skip e.g. libraries that return [`Error::PermissionDenied`] and keep
+    /// reading the rest.
     ///
     /// # Example
     ///
@@ -233,8 +571,108 @@ impl SteamDir {
     /// # Ok::<_, TestError>(())
     /// ```
     pub fn libraries(&self) -> Result<library::Iter> {
-        let paths = self.library_paths()?;
-        Ok(library::Iter::new(paths))
+        let folders = self.library_folders()?;
+        Ok(library::Iter::new(folders))
+    }
+
+    /// Like [`SteamDir::libraries()`], but the scan can be stopped early through `cancel`
+    ///
+    /// `cancel` is checked before reading each library. Once it's set the iterator returns an
+    /// [`Error::Cancelled`] and then ends. This is useful for aborting a scan running on another
+    /// thread e.g. when a network-mounted library is slow to respond
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use steamlocate::Error;
+    ///
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let cancel = AtomicBool::new(false);
+    /// for library in steam_dir.libraries_cancellable(&cancel)? {
+    ///     match library {
+    ///         Ok(library) => println!("Library - {}", library.path().display()),
+    ///         Err(Error::Cancelled) => break,
+    ///         Err(err) => return Err(err.into()),
+    ///     }
+    ///     // This would typically be set from another thread
+    ///     cancel.store(true, Ordering::Relaxed);
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn libraries_cancellable<'cancel>(
+        &self,
+        cancel: &'cancel AtomicBool,
+    ) -> Result<cancel::Cancellable<'cancel, library::Iter>> {
+        let libraries = self.libraries()?;
+        Ok(cancel::Cancellable::new(libraries, cancel))
+    }
+
+    /// Returns an [`Iterator`] over the [`Library`]s of this installation with duplicates removed
+    ///
+    /// `libraryfolders.vdf` can list the same directory more than once (e.g. once directly and
+    /// once through a symlink). This canonicalizes each library path and skips any that resolve to
+    /// an already seen directory while preserving the original order. Paths that fail to
+    /// canonicalize are compared as-is
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// let num_apps: usize = steam_dir
+    ///     .libraries_deduped()?
+    ///     .filter_map(Result::ok)
+    ///     .map(|lib| lib.app_ids().len())
+    ///     .sum();
+    /// println!("Wow you have {num_apps} installed!");
+    /// # assert_eq!(num_apps, 3);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn libraries_deduped(&self) -> Result<library::Iter> {
+        let mut seen = BTreeSet::new();
+        let folders = self
+            .library_folders()?
+            .into_iter()
+            .filter(|folder| {
+                let resolved =
+                    fs::canonicalize(&folder.path).unwrap_or_else(|_| folder.path.clone());
+                seen.insert(resolved)
+            })
+            .collect();
+        Ok(library::Iter::new(folders))
+    }
+
+    /// Returns the number of [`Library`]s listed for this installation
+    ///
+    /// Unlike [`SteamDir::libraries()`] this only parses `libraryfolders.vdf` and doesn't read
+    /// each library's directory
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// let num_libraries = steam_dir.library_count()?;
+    /// println!("Steam knows about {num_libraries} libraries");
+    /// # assert_eq!(num_libraries, 2);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn library_count(&self) -> Result<usize> {
+        self.library_paths().map(|paths| paths.len())
     }
 
     /// Convenient helper to look through all the libraries for a specific app
@@ -259,47 +697,1072 @@ impl SteamDir {
         self.libraries()?
             .filter_map(|library| library.ok())
             .find_map(|lib| {
-                lib.app(app_id)
+                lib.installed_app(app_id)
                     .map(|maybe_app| maybe_app.map(|app| (app, lib)))
             })
             .transpose()
     }
 
-    // TODO: `Iterator`ify this
-    pub fn compat_tool_mapping(&self) -> Result<HashMap<u32, CompatTool>> {
-        let config_path = self.path.join("config").join("config.vdf");
-        let vdf_text =
-            fs::read_to_string(&config_path).map_err(|io| Error::io(io, &config_path))?;
-        let store: config::Store = keyvalues_serde::from_str(&vdf_text).map_err(|de| {
-            Error::parse(
-                ParseErrorKind::Config,
-                ParseError::from_serde(de),
-                &config_path,
-            )
-        })?;
+    /// Returns the installation directory of the app with `app_id`
+    ///
+    /// This is a shortcut for [`SteamDir::find_app()`] followed by
+    /// [`Library::resolve_app_dir()`]. Returns [`None`] if the app isn't installed or its
+    /// installation directory doesn't exist
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let gmod_dir = steam_dir.app_install_dir(GMOD)?.unwrap();
+    /// assert!(gmod_dir.ends_with("GarrysMod"));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn app_install_dir(&self, app_id: u32) -> Result<Option<PathBuf>> {
+        let maybe_dir = self
+            .find_app(app_id)?
+            .map(|(app, library)| library.resolve_app_dir(&app))
+            .filter(|app_dir| app_dir.is_dir());
+        Ok(maybe_dir)
+    }
 
-        Ok(store.software.valve.steam.mapping)
+    /// Returns the library that `path` lives within, if any
+    ///
+    /// Both `path` and each library's root get canonicalized before comparing, so symlinked
+    /// libraries are handled correctly. Libraries that fail to parse are skipped and if
+    /// libraries happen to be nested then the innermost one is returned
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Io`] if `path` can't be canonicalized (e.g. because it doesn't exist)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let gmod_dir = steam_dir.app_install_dir(GMOD)?.unwrap();
+    /// let library = steam_dir.find_library_containing(&gmod_dir)?.unwrap();
+    /// assert!(library.app_ids().contains(&GMOD));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn find_library_containing(&self, path: &Path) -> Result<Option<Library>> {
+        let path = path.canonicalize().map_err(|io| Error::io(io, path))?;
+        let maybe_library = self
+            .libraries()?
+            .filter_map(|library| library.ok())
+            .filter_map(|library| {
+                let root = library.path().canonicalize().ok()?;
+                path.starts_with(&root)
+                    .then(|| (root.components().count(), library))
+            })
+            .max_by_key(|(depth, _)| *depth)
+            .map(|(_, library)| library);
+        Ok(maybe_library)
     }
 
-    /// Returns an [`Iterator`] of all [`Shortcut`]s aka non-Steam games that were added to steam
+    /// Looks up several apps at once while only walking the libraries a single time
+    ///
+    /// This is much cheaper than calling [`SteamDir::find_app()`] for each ID since only the
+    /// manifests for the requested apps get parsed. The returned map only contains entries for
+    /// the apps that were actually found
     ///
     /// # Example
     ///
     /// ```
     /// # use steamlocate::__private_tests::prelude::*;
-    /// # let moonlighter = SampleShortcuts::JustGogMoonlighter;
-    /// # let temp_steam_dir: TempSteamDir = moonlighter.try_into()?;
+    /// # let temp_steam_dir = expect_test_env();
     /// # let steam_dir = temp_steam_dir.steam_dir();
     /// # /*
     /// let steam_dir = SteamDir::locate()?;
     /// # */
-    /// let mut shortcuts_iter = steam_dir.shortcuts()?;
-    /// let moonlighter = shortcuts_iter.next().unwrap()?;
-    /// assert_eq!(moonlighter.app_name, "Moonlighter");
-    /// assert!(moonlighter.executable.ends_with("Moonlighter/start.sh\""));
+    /// const GMOD: u32 = 4_000;
+    /// const WARFRAME: u32 = 230_410;
+    /// const NOT_INSTALLED: u32 = 1;
+    /// let found = steam_dir.find_apps(&[GMOD, WARFRAME, NOT_INSTALLED])?;
+    /// assert_eq!(found.len(), 2);
+    /// let (warframe, _library) = &found[&WARFRAME];
+    /// assert_eq!(warframe.app_id, WARFRAME);
     /// # Ok::<_, TestError>(())
     /// ```
-    pub fn shortcuts(&self) -> Result<shortcut::Iter> {
-        shortcut::Iter::new(&self.path)
+    pub fn find_apps(&self, app_ids: &[u32]) -> Result<HashMap<u32, (App, Library)>> {
+        let mut remaining: BTreeSet<u32> = app_ids.iter().copied().collect();
+        let mut found = HashMap::new();
+
+        for library in self.libraries()?.filter_map(|library| library.ok()) {
+            if remaining.is_empty() {
+                break;
+            }
+
+            let matching: Vec<u32> = library
+                .app_ids()
+                .iter()
+                .copied()
+                .filter(|app_id| remaining.contains(app_id))
+                .collect();
+            for app_id in matching {
+                if let Some(app) = library.installed_app(app_id) {
+                    remaining.remove(&app_id);
+                    found.insert(app_id, (app?, library.clone()));
+                }
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Returns the IDs of every app across all libraries without parsing any manifests
+    ///
+    /// This is the cheapest way to check what's installed. The IDs are sorted and deduplicated
+    /// (e.g. for libraries that are listed more than once). Like [`SteamDir::find_app()`],
+    /// libraries that fail to load are skipped
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let app_ids = steam_dir.app_ids()?;
+    /// assert_eq!(app_ids, [4_000, 230_410, 599_140]);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn app_ids(&self) -> Result<Vec<u32>> {
+        let app_ids: BTreeSet<u32> = self
+            .libraries()?
+            .filter_map(|library| library.ok())
+            .flat_map(|library| library.app_ids().to_owned())
+            .collect();
+        Ok(app_ids.into_iter().collect())
+    }
+
+    /// Returns every installed [`App`] across all libraries along with the [`Library`] that
+    /// contains it
+    ///
+    /// Apps are listed in library order. Libraries that fail to load and apps that fail to parse
+    /// are passed through as errors while apps that are missing their manifest are skipped
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for maybe_app in steam_dir.apps()? {
+    ///     let (app, library) = maybe_app?;
+    ///     println!("{:?} is in {}", app.name, library.path().display());
+    /// }
+    /// # assert_eq!(steam_dir.apps()?.count(), 3);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn apps(&self) -> Result<impl Iterator<Item = Result<(App, Library)>>> {
+        let apps = self.libraries()?.flat_map(|maybe_library| {
+            let (library, err) = match maybe_library {
+                Ok(library) => (Some(library), None),
+                Err(err) => (None, Some(Err(err))),
+            };
+            let apps = library.into_iter().flat_map(|library| {
+                let app_ids = library.app_ids().to_owned();
+                app_ids.into_iter().filter_map(move |app_id| {
+                    library
+                        .installed_app(app_id)
+                        .map(|maybe_app| maybe_app.map(|app| (app, library.clone())))
+                })
+            });
+            err.into_iter().chain(apps)
+        });
+        Ok(apps)
+    }
+
+    /// Returns every installed [`App`] along with its [`Library`] sorted by
+    /// [`last_updated`][App::last_updated] from newest to oldest
+    ///
+    /// This eagerly reads and parses every app manifest across all libraries before sorting, so
+    /// prefer [`SteamDir::apps()`] when the order doesn't matter. Apps without a last updated time
+    /// sort last and ties are broken by [`app_id`][App::app_id]. The first error that's
+    /// encountered is returned
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for (app, _library) in steam_dir.apps_sorted_by_updated()?.into_iter().take(5) {
+    ///     println!("Recently updated: {}", app.display_name());
+    /// }
+    /// # let names: Vec<_> = steam_dir
+    /// #     .apps_sorted_by_updated()?
+    /// #     .into_iter()
+    /// #     .map(|(app, _)| app.display_name().to_owned())
+    /// #     .collect();
+    /// # assert_eq!(names, ["Garry's Mod", "Graveyard Keeper", "Warframe"]);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn apps_sorted_by_updated(&self) -> Result<Vec<(App, Library)>> {
+        let mut apps = self.apps()?.collect::<Result<Vec<_>>>()?;
+        // `None` is less than any `Some(_)`, so it ends up last when sorting in reverse
+        apps.sort_by(|(a, _), (b, _)| {
+            b.last_updated
+                .cmp(&a.last_updated)
+                .then_with(|| a.app_id.cmp(&b.app_id))
+        });
+        Ok(apps)
+    }
+
+    /// Scans every app in every library while collecting any errors along the way
+    ///
+    /// Unlike [`SteamDir::apps()`] this reads everything up front. Failures don't stop the scan,
+    /// so you get all of the apps that could be parsed along with diagnostics for everything that
+    /// couldn't
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let scan = steam_dir.scan_apps()?;
+    /// for (app, _library) in &scan.apps {
+    ///     println!("Found {:?}", app.name);
+    /// }
+    /// for (app_id, err) in &scan.errors {
+    ///     println!("Failed loading {app_id}: {err}");
+    /// }
+    /// # assert_eq!(scan.apps.len(), 3);
+    /// # assert!(scan.is_complete());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn scan_apps(&self) -> Result<ScanResult> {
+        let mut scan = ScanResult::default();
+        for maybe_library in self.libraries()? {
+            let library = match maybe_library {
+                Ok(library) => library,
+                Err(err) => {
+                    scan.library_errors.push(err);
+                    continue;
+                }
+            };
+
+            let app_ids = library.app_ids().iter();
+            for &app_id in app_ids.chain(library.listed_without_manifest()) {
+                match library.app(app_id) {
+                    Some(Ok(app)) => scan.apps.push((app, library.clone())),
+                    Some(Err(err)) => scan.errors.push((app_id, err)),
+                    None => {}
+                }
+            }
+        }
+
+        Ok(scan)
+    }
+
+    /// Like [`SteamDir::apps()`], but skips over tools like Proton and the Steam Linux Runtime
+    ///
+    /// Tools are detected with [`App::is_tool()`]. This is usually what you want when showing
+    /// a user their games
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for maybe_game in steam_dir.games()? {
+    ///     let (game, _library) = maybe_game?;
+    ///     # assert!(!game.is_tool());
+    ///     println!("{:?}", game.name);
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn games(&self) -> Result<impl Iterator<Item = Result<(App, Library)>>> {
+        let games = self.apps()?.filter(|maybe_app| match maybe_app {
+            Ok((app, _)) => !app.is_tool(),
+            Err(_) => true,
+        });
+        Ok(games)
+    }
+
+    /// Like [`SteamDir::apps()`], but only includes dedicated servers
+    ///
+    /// Dedicated servers are detected with [`App::is_dedicated_server()`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for maybe_server in steam_dir.server_apps()? {
+    ///     let (server, library) = maybe_server?;
+    ///     println!("{:?}", library.resolve_app_dir(&server));
+    /// }
+    /// # assert_eq!(steam_dir.server_apps()?.count(), 0);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn server_apps(&self) -> Result<impl Iterator<Item = Result<(App, Library)>>> {
+        let servers = self.apps()?.filter(|maybe_app| match maybe_app {
+            Ok((app, _)) => app.is_dedicated_server(),
+            Err(_) => true,
+        });
+        Ok(servers)
+    }
+
+    // TODO: `Iterator`ify this
+    pub fn compat_tool_mapping(&self) -> Result<HashMap<u32, CompatTool>> {
+        let store = self.read_config()?;
+        Ok(store.software.valve.steam.mapping)
+    }
+
+    /// Returns the compatibility tool (e.g. Proton) that Steam will use to run `app_id`
+    ///
+    /// This is the app's own entry in the [compat tool mapping][SteamDir::compat_tool_mapping]
+    /// if it has one, otherwise the global default that's listed under an app ID of `0`. Entries
+    /// without a tool [`name`][CompatTool::name] are skipped. Returns [`None`] when neither is set,
+    /// which means a Windows-only game won't run on Linux
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GRAVEYARD_KEEPER: u32 = 599_140;
+    /// match steam_dir.effective_compat_tool(GRAVEYARD_KEEPER)? {
+    ///     Some(tool) => println!("Graveyard Keeper runs through {:?}", tool.name),
+    ///     None => println!("Graveyard Keeper runs natively"),
+    /// }
+    /// # let tool = steam_dir.effective_compat_tool(GRAVEYARD_KEEPER)?.unwrap();
+    /// # assert_eq!(tool.name.as_deref(), Some("proton_experimental"));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn effective_compat_tool(&self, app_id: u32) -> Result<Option<CompatTool>> {
+        let mut mapping = self.compat_tool_mapping()?;
+        let has_name =
+            |tool: &CompatTool| tool.name.as_deref().is_some_and(|name| !name.is_empty());
+        let tool = mapping
+            .remove(&app_id)
+            .filter(has_name)
+            .or_else(|| mapping.remove(&0).filter(has_name));
+        Ok(tool)
+    }
+
+    /// Returns Steam's global auto-update window from `config/config.vdf`
+    ///
+    /// Returns [`None`] if the window has never been configured
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let window = steam_dir.auto_update_window()?.unwrap();
+    /// if window.enabled {
+    ///     println!(
+    ///         "Only updating between hours {:?} and {:?}",
+    ///         window.start_hour, window.end_hour,
+    ///     );
+    /// }
+    /// # assert_eq!((window.start_hour, window.end_hour), (Some(2), Some(6)));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn auto_update_window(&self) -> Result<Option<AutoUpdateWindow>> {
+        let store = self.read_config()?;
+        Ok(store.software.valve.steam.auto_update_window())
+    }
+
+    /// Returns Steam's global download bandwidth settings from `config/config.vdf`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let bandwidth = steam_dir.bandwidth_settings()?;
+    /// match bandwidth.download_throttle_kbps {
+    ///     Some(kbps) => println!("Downloads are limited to {kbps} kbps"),
+    ///     None => println!("Downloads are unlimited"),
+    /// }
+    /// if !bandwidth.allow_downloads_during_gameplay {
+    ///     println!("Downloads are paused while playing");
+    /// }
+    /// # assert_eq!(bandwidth.download_throttle_kbps, None);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn bandwidth_settings(&self) -> Result<BandwidthSettings> {
+        let store = self.read_config()?;
+        Ok(store.software.valve.steam.bandwidth_settings())
+    }
+
+    /// When `app`'s [scheduled auto-update][App::scheduled_auto_update] is expected to start
+    /// once Steam's global [auto-update window][SteamDir::auto_update_window] is accounted for
+    ///
+    /// A scheduled time that falls outside of the window is moved to when the window next opens.
+    /// The window's hours are in the machine's local time, so `utc_offset_secs` is needed to
+    /// resolve them (see [`AutoUpdateWindow::next_allowed()`]). Returns [`None`] when the app
+    /// doesn't have an update scheduled
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = TempSteamDir::builder()
+    /// #     .app(SampleApp::GraveyardKeeper.into())
+    /// #     .config(SampleConfig::Linux)
+    /// #     .finish()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GRAVEYARD_KEEPER: u32 = 599_140;
+    /// let (graveyard_keeper, _library) = steam_dir.find_app(GRAVEYARD_KEEPER)?.unwrap();
+    /// // UTC-5
+    /// let utc_offset_secs = -5 * 60 * 60;
+    /// if let Some(update) = steam_dir.next_scheduled_update(&graveyard_keeper, utc_offset_secs)? {
+    ///     println!("Graveyard Keeper will update at {update:?}");
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn next_scheduled_update(
+        &self,
+        app: &App,
+        utc_offset_secs: i32,
+    ) -> Result<Option<std::time::SystemTime>> {
+        let Some(scheduled) = app.scheduled_auto_update else {
+            return Ok(None);
+        };
+        let next = match self.auto_update_window()? {
+            Some(window) => window.next_allowed(scheduled, utc_offset_secs),
+            None => scheduled,
+        };
+        Ok(Some(next))
+    }
+
+    /// Returns the accounts configured in `config/config.vdf` mapped from account name to
+    /// SteamID64
+    ///
+    /// This includes accounts that have logged in before even if they aren't currently
+    /// remembered by Steam. Accounts without a recorded SteamID are skipped
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for (account_name, steam_id) in steam_dir.configured_accounts()? {
+    ///     println!("{account_name} - {steam_id}");
+    /// }
+    /// # assert_eq!(
+    /// #     steam_dir.configured_accounts()?.get("someone_else"),
+    /// #     Some(&76_561_198_083_388_851),
+    /// # );
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn configured_accounts(&self) -> Result<HashMap<String, u64>> {
+        let store = self.read_config()?;
+        let accounts = store
+            .software
+            .valve
+            .steam
+            .accounts
+            .into_iter()
+            .filter_map(|(name, account)| Some((name, account.steam_id?)))
+            .collect();
+        Ok(accounts)
+    }
+
+    /// Returns the language that Steam is configured to use from `config/config.vdf`
+    ///
+    /// This is Steam's own name for the language (e.g. `english` or `schinese`) rather than a
+    /// language code. Returns [`None`] if no language is set
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let language = steam_dir.language()?.unwrap_or_else(|| "english".to_owned());
+    /// println!("Steam is using {language}");
+    /// # assert_eq!(language, "english");
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn language(&self) -> Result<Option<String>> {
+        let store = self.read_config()?;
+        Ok(store.software.valve.steam.language)
+    }
+
+    /// Returns the name of the custom skin that Steam is using from `config/config.vdf`
+    ///
+    /// This is the name of the skin's directory within [`SteamDir::available_skins()`]. Newer
+    /// clients store it as `SkinV5` while older ones used `SkinV4`, so the newer one is preferred.
+    /// Returns [`None`] when the default skin is used
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// match steam_dir.active_skin()? {
+    ///     Some(skin) => println!("Steam is using the {skin} skin"),
+    ///     None => println!("Steam is using the default skin"),
+    /// }
+    /// # assert_eq!(steam_dir.active_skin()?, None);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn active_skin(&self) -> Result<Option<String>> {
+        let store = self.read_config()?;
+        Ok(store.software.valve.steam.active_skin())
+    }
+
+    /// Returns the names of the skins that are installed in Steam's `skins` directory
+    ///
+    /// Every subdirectory is treated as a skin, so stray files like Steam's `skins/Readme.txt`
+    /// are skipped. The names are sorted and the list is empty when there's no `skins` directory
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// # std::fs::create_dir_all(steam_dir.path().join("skins").join("Metro"))?;
+    /// for skin in steam_dir.available_skins()? {
+    ///     println!("{skin} is installed");
+    /// }
+    /// # assert_eq!(steam_dir.available_skins()?, ["Metro"]);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn available_skins(&self) -> Result<Vec<String>> {
+        let skins_dir = self.path.join("skins");
+        let entries = match fs::read_dir(&skins_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(Error::read_dir(err, &skins_dir)),
+        };
+
+        let mut skins = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|io| Error::read_dir(io, &skins_dir))?;
+            if entry.path().is_dir() {
+                skins.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        skins.sort_unstable();
+        Ok(skins)
+    }
+
+    /// The path to Steam's `config.vdf`
+    ///
+    /// Some Linux installs keep it behind an extra `steam` directory, so
+    /// `steam/config/config.vdf` is used when the usual file is missing. The usual path is
+    /// returned when neither exists, so that errors point at the expected location
+    fn config_vdf_path(&self) -> PathBuf {
+        let config_path = self.config_dir().join("config.vdf");
+        if config_path.is_file() {
+            return config_path;
+        }
+
+        let nested_path = self.path.join("steam").join("config").join("config.vdf");
+        if nested_path.is_file() {
+            trace::debug!("using nested config at {}", nested_path.display());
+            nested_path
+        } else {
+            config_path
+        }
+    }
+
+    fn read_config(&self) -> Result<config::Store> {
+        let config_path = self.config_vdf_path();
+        let vdf_text =
+            fs::read_to_string(&config_path).map_err(|io| Error::io(io, &config_path))?;
+        keyvalues_serde::from_str(&vdf_text).map_err(|de| {
+            Error::parse(
+                ParseErrorKind::Config,
+                ParseError::from_serde(de),
+                &config_path,
+            )
+        })
+    }
+
+    /// Returns the account ID and `userdata` directory of the most recently logged in user
+    ///
+    /// The most recent account is read from `config/loginusers.vdf` and its SteamID64 is converted
+    /// to the 32-bit account ID that Steam uses to name the user's `userdata/<ACCOUNT_ID>`
+    /// directory. Returns [`None`] when no account is marked as the most recent, when that account
+    /// doesn't have a `userdata` directory, or when `loginusers.vdf` doesn't exist because nobody
+    /// has logged in yet
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// # let config_dir = steam_dir.config_dir();
+    /// # std::fs::create_dir_all(&config_dir)?;
+    /// # std::fs::write(
+    /// #     config_dir.join("loginusers.vdf"),
+    /// #     r#""users" { "76561198083388851" { "MostRecent" "1" } }"#,
+    /// # )?;
+    /// if let Some((account_id, user_dir)) = steam_dir.most_recent_user()? {
+    ///     println!("Account {account_id}'s data is stored at {}", user_dir.display());
+    /// }
+    /// # assert_eq!(steam_dir.most_recent_user()?.unwrap().0, 123_123_123);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn most_recent_user(&self) -> Result<Option<(u32, PathBuf)>> {
+        let login_users_path = self.config_dir().join("loginusers.vdf");
+        let vdf_text = match fs::read_to_string(&login_users_path) {
+            Ok(vdf_text) => vdf_text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(Error::io(err, &login_users_path)),
+        };
+        let login_users: config::LoginUsers =
+            keyvalues_serde::from_str(&vdf_text).map_err(|de| {
+                Error::parse(
+                    ParseErrorKind::LoginUsers,
+                    ParseError::from_serde(de),
+                    &login_users_path,
+                )
+            })?;
+
+        let Some(steam_id) = login_users
+            .into_iter()
+            .find_map(|(steam_id, user)| user.most_recent.then_some(steam_id))
+        else {
+            return Ok(None);
+        };
+        // The account ID is stored in the lower 32 bits of the SteamID64
+        let account_id = (steam_id & 0xFFFF_FFFF) as u32;
+        let user_dir = self.userdata_dir().join(account_id.to_string());
+        Ok(user_dir.is_dir().then_some((account_id, user_dir)))
+    }
+
+    /// Returns the SteamID64s of every user on this machine that has `app_id` configured
+    ///
+    /// Each user's `userdata/<ACCOUNT_ID>/config/localconfig.vdf` is checked for an entry for the
+    /// app, which Steam adds once a user launches or configures it. Along with the manifest's
+    /// [`last_user`][App::last_user] this covers users that play the game through Family
+    /// Sharing. Users without a `localconfig.vdf`, or whose `localconfig.vdf` can't be read, are
+    /// skipped so that one broken profile doesn't hide everyone else. IDs are sorted
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// for steam_id in steam_dir.app_users(GMOD)? {
+    ///     println!("{steam_id} can play Garry's Mod");
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn app_users(&self, app_id: u32) -> Result<Vec<u64>> {
+        // The base value of an individual account's SteamID64 in the public universe
+        const STEAM_ID64_BASE: u64 = 0x0110_0001_0000_0000;
+
+        let user_data = self.userdata_dir();
+        let entries = fs::read_dir(&user_data).map_err(|io| Error::read_dir(io, &user_data))?;
+
+        let mut users = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|io| Error::read_dir(io, &user_data))?;
+            let Some(account_id) = config::user_data_account_id(&entry.file_name()) else {
+                continue;
+            };
+
+            let local_config = entry.path().join("config").join("localconfig.vdf");
+            if !local_config.is_file() {
+                continue;
+            }
+            match config::local_config_app_ids(&local_config) {
+                Ok(app_ids) if app_ids.contains(&app_id) => {
+                    users.push(STEAM_ID64_BASE | u64::from(account_id));
+                }
+                Ok(_) => {}
+                Err(_err) => {
+                    trace::debug!("skipping {}: {_err}", local_config.display());
+                }
+            }
+        }
+
+        users.sort_unstable();
+        Ok(users)
+    }
+
+    /// Returns the launch options that the [most recent user][Self::most_recent_user] set for
+    /// `app_id`
+    ///
+    /// _See [`SteamDir::launch_options_for_user()`] for more info_. Returns [`None`] when there is
+    /// no most recent user
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// # let config_dir = steam_dir.config_dir();
+    /// # std::fs::create_dir_all(&config_dir)?;
+    /// # std::fs::write(
+    /// #     config_dir.join("loginusers.vdf"),
+    /// #     r#""users" { "76561198083388851" { "MostRecent" "1" } }"#,
+    /// # )?;
+    /// const GMOD: u32 = 4_000;
+    /// match steam_dir.launch_options(GMOD)? {
+    ///     Some(launch_options) => println!("Garry's Mod is launched with {launch_options}"),
+    ///     None => println!("Garry's Mod is launched without any extra options"),
+    /// }
+    /// # assert_eq!(steam_dir.launch_options(GMOD)?, None);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn launch_options(&self, app_id: u32) -> Result<Option<String>> {
+        match self.most_recent_user()? {
+            Some((user_id, _user_dir)) => self.launch_options_for_user(app_id, user_id),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the launch options that the user with the account ID `user_id` set for `app_id`
+    ///
+    /// Launch options for Steam apps are read from the user's
+    /// `userdata/<USER_ID>/config/localconfig.vdf`. If `app_id` is a [`Shortcut`]'s ID then its
+    /// [`launch_options()`][Shortcut::launch_options] are used instead. Returns [`None`] when no
+    /// launch options are set or the user doesn't have a `userdata` directory
+    pub fn launch_options_for_user(&self, app_id: u32, user_id: u32) -> Result<Option<String>> {
+        let user_dir = self.userdata_dir().join(user_id.to_string());
+        if !user_dir.is_dir() {
+            return Ok(None);
+        }
+
+        let local_config = user_dir.join("config").join("localconfig.vdf");
+        if local_config.is_file() {
+            if let Some(launch_options) =
+                config::local_config_launch_options(&local_config, app_id)?
+            {
+                return Ok(Some(launch_options));
+            }
+        }
+
+        // Non-Steam games keep theirs in `shortcuts.vdf` instead
+        for shortcut in self.shortcuts_for_user(user_id)? {
+            let shortcut = shortcut?;
+            if shortcut.app_id == app_id && !shortcut.launch_options().is_empty() {
+                return Ok(Some(shortcut.launch_options().to_owned()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the files that Steam Cloud tracks for `app_id` across every user
+    ///
+    /// Each user's inventory is read from `userdata/<USER_ID>/<APP_ID>/remotecache.vdf`. Users
+    /// that have never synced the app, or whose `remotecache.vdf` can't be read, are skipped so that
+    /// one broken profile doesn't hide everyone else. Files are sorted by user and then path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// for file in steam_dir.cloud_files(GMOD)? {
+    ///     println!("{} has {} ({:?} bytes)", file.user_id, file.path, file.size);
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn cloud_files(&self, app_id: u32) -> Result<Vec<cloud::CloudFile>> {
+        let user_data = self.userdata_dir();
+        let entries = fs::read_dir(&user_data).map_err(|io| Error::read_dir(io, &user_data))?;
+
+        let mut files = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|io| Error::read_dir(io, &user_data))?;
+            let Some(user_id) = config::user_data_account_id(&entry.file_name()) else {
+                continue;
+            };
+
+            let remote_cache = entry
+                .path()
+                .join(app_id.to_string())
+                .join("remotecache.vdf");
+            if !remote_cache.is_file() {
+                continue;
+            }
+            match cloud::parse_remote_cache(&remote_cache, user_id) {
+                Ok(user_files) => files.extend(user_files),
+                Err(_err) => {
+                    trace::debug!("skipping {}: {_err}", remote_cache.display());
+                }
+            }
+        }
+
+        files.sort_by(|a, b| (a.user_id, &a.path).cmp(&(b.user_id, &b.path)));
+        Ok(files)
+    }
+
+    /// Returns the version of the installed Steam client
+    ///
+    /// This is read from the `package/steam_client_<PLATFORM>.manifest` file that Steam keeps
+    /// up to date. The manifest for the current platform is preferred when there are several.
+    /// Returns [`None`] if there's no client manifest or it doesn't include a version
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// match steam_dir.client_version()? {
+    ///     Some(version) => println!("Running Steam client version {version}"),
+    ///     None => println!("Couldn't determine the Steam client version"),
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn client_version(&self) -> Result<Option<String>> {
+        const PLATFORM: &str = if cfg!(windows) {
+            "win"
+        } else if cfg!(target_os = "macos") {
+            "osx"
+        } else {
+            "ubuntu"
+        };
+
+        let package_dir = self.path.join("package");
+        let entries = match fs::read_dir(&package_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(Error::read_dir(err, &package_dir)),
+        };
+        let mut manifests = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|io| Error::read_dir(io, &package_dir))?;
+            let file_name = entry.file_name();
+            let Some(platform) = file_name
+                .to_str()
+                .and_then(|name| name.strip_prefix("steam_client_"))
+                .and_then(|name| name.strip_suffix(".manifest"))
+            else {
+                continue;
+            };
+            let is_current_platform = platform.starts_with(PLATFORM);
+            manifests.push((!is_current_platform, entry.path()));
+        }
+        // Sorts manifests for the current platform first
+        manifests.sort();
+
+        let Some((_, manifest_path)) = manifests.into_iter().next() else {
+            return Ok(None);
+        };
+        let vdf_text =
+            fs::read_to_string(&manifest_path).map_err(|io| Error::io(io, &manifest_path))?;
+        let manifest: config::ClientManifest =
+            keyvalues_serde::from_str(&vdf_text).map_err(|de| {
+                Error::parse(
+                    ParseErrorKind::ClientManifest,
+                    ParseError::from_serde(de),
+                    &manifest_path,
+                )
+            })?;
+        Ok(manifest.version)
+    }
+
+    /// Checks whether achievement/stats data is available locally for the app
+    ///
+    /// This is a cheap filesystem probe that looks for either Steam's cached stats schema
+    /// (`appcache/stats/UserGameStatsSchema_<APP_ID>.bin`) or a per-user stats directory
+    /// (`userdata/<USER_ID>/<APP_ID>/stats`). The schema itself is not parsed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// if steam_dir.has_stats(GMOD) {
+    ///     println!("Garry's Mod has stats available");
+    /// }
+    /// # assert!(!steam_dir.has_stats(GMOD));
+    /// ```
+    pub fn has_stats(&self, app_id: u32) -> bool {
+        let schema = self
+            .path
+            .join("appcache")
+            .join("stats")
+            .join(format!("UserGameStatsSchema_{app_id}.bin"));
+        if schema.is_file() {
+            return true;
+        }
+
+        let Ok(user_dirs) = fs::read_dir(self.userdata_dir()) else {
+            return false;
+        };
+        let app_id = app_id.to_string();
+        user_dirs
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| config::user_data_account_id(&entry.file_name()).is_some())
+            .any(|entry| entry.path().join(&app_id).join("stats").is_dir())
+    }
+
+    /// Returns an [`Iterator`] of all [`Shortcut`]s aka non-Steam games that were added to steam
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let moonlighter = SampleShortcuts::JustGogMoonlighter;
+    /// # let temp_steam_dir: TempSteamDir = moonlighter.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let mut shortcuts_iter = steam_dir.shortcuts()?;
+    /// let moonlighter = shortcuts_iter.next().unwrap()?;
+    /// assert_eq!(moonlighter.app_name, "Moonlighter");
+    /// assert!(moonlighter.executable.ends_with("Moonlighter/start.sh\""));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn shortcuts(&self) -> Result<shortcut::Iter> {
+        shortcut::Iter::new(&self.path)
+    }
+
+    /// Like [`SteamDir::shortcuts()`], but only for the user with the account ID `user_id`
+    ///
+    /// Only `userdata/<USER_ID>/config/shortcuts.vdf` is read. The iterator is empty if the user
+    /// hasn't added any shortcuts, and an error is returned if the user has no `userdata`
+    /// directory at all
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// # let user_id = 123_123_123;
+    /// # /*
+    /// let (user_id, _user_dir) = steam_dir.most_recent_user()?.unwrap();
+    /// # */
+    /// for shortcut in steam_dir.shortcuts_for_user(user_id)? {
+    ///     println!("{user_id} added {}", shortcut?.app_name);
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn shortcuts_for_user(&self, user_id: u32) -> Result<shortcut::Iter> {
+        shortcut::Iter::for_user(&self.path, user_id)
+    }
+
+    /// Returns an iterator over every installed app followed by every non-Steam shortcut
+    ///
+    /// This chains [`SteamDir::apps()`] with [`SteamDir::shortcuts()`], so errors from either are
+    /// passed through the same way. An installation without a `userdata` directory simply has no
+    /// shortcuts
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for maybe_entry in steam_dir.all_entries()? {
+    ///     let entry = maybe_entry?;
+    ///     println!("{} - {}", entry.name(), entry.launch_url());
+    /// }
+    /// # assert_eq!(steam_dir.all_entries()?.count(), 3);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn all_entries(&self) -> Result<impl Iterator<Item = Result<Entry>>> {
+        let apps = self
+            .apps()?
+            .map(|maybe_app| maybe_app.map(|(app, library)| Entry::App(app, library)));
+        let shortcuts = self
+            .userdata_dir_checked()
+            .map(|_| self.shortcuts())
+            .transpose()?
+            .into_iter()
+            .flatten()
+            .map(|maybe_shortcut| maybe_shortcut.map(Entry::Shortcut));
+        Ok(apps.chain(shortcuts))
     }
 }
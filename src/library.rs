@@ -5,17 +5,25 @@
 //! [`Library::from_dir()`].
 
 use std::{
+    collections::{hash_map::RandomState, BTreeMap, BTreeSet, HashSet},
+    ffi::OsStr,
     fs,
+    hash::{BuildHasher, Hasher},
+    io,
     path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::AtomicBool,
+    time::SystemTime,
 };
 
 use crate::{
     app,
-    error::{ParseError, ParseErrorKind},
-    App, Error, Result,
+    cancel::Cancellable,
+    error::{ParseError, ParseErrorKind, ValidationError},
+    reveal, trace, App, Error, Result,
 };
 
-use keyvalues_parser::Vdf;
+use keyvalues_parser::{Obj, Value, Vdf};
 
 /// Discovers all the steam libraries from `libraryfolders.vdf`
 ///
@@ -48,7 +56,7 @@ use keyvalues_parser::Vdf;
 ///     ...
 /// }
 /// ```
-pub(crate) fn parse_library_paths(path: &Path) -> Result<Vec<PathBuf>> {
+pub(crate) fn parse_library_folders(path: &Path) -> Result<Vec<LibraryFolder>> {
     let parse_error = |err| Error::parse(ParseErrorKind::LibraryFolders, err, path);
 
     if !path.is_file() {
@@ -62,35 +70,317 @@ pub(crate) fn parse_library_paths(path: &Path) -> Result<Vec<PathBuf>> {
     let obj = value
         .get_obj()
         .ok_or_else(|| parse_error(ParseError::unexpected_structure()))?;
-    let paths: Vec<_> = obj
+    let folders: Vec<_> = obj
         .iter()
         .filter(|(key, _)| key.parse::<u32>().is_ok())
         .map(|(_, values)| {
-            values
+            let folder_obj = values
                 .first()
                 .and_then(|value| value.get_obj())
-                .and_then(|obj| obj.get("path"))
+                .ok_or_else(|| parse_error(ParseError::unexpected_structure()))?;
+            let path = folder_obj
+                .get("path")
                 .and_then(|values| values.first())
                 .and_then(|value| value.get_str())
                 .ok_or_else(|| parse_error(ParseError::unexpected_structure()))
-                .map(PathBuf::from)
+                .map(|path| normalize_path(PathBuf::from(path)))?;
+            let app_ids = folder_obj
+                .get("apps")
+                .and_then(|values| values.first())
+                .and_then(|value| value.get_obj())
+                .map(|apps| apps.keys().filter_map(|key| key.parse().ok()).collect())
+                .unwrap_or_default();
+            Ok(LibraryFolder { path, app_ids })
         })
-        .collect::<Result<_>>()?;
+        .collect::<Result<Vec<_>>>()?;
+
+    trace::debug!(
+        "parsed {} library folders from {}",
+        folders.len(),
+        path.display(),
+    );
+    Ok(folders)
+}
+
+/// Adds or updates the entries for `libraries` in the `libraryfolders.vdf` file at `path`
+///
+/// Entries are matched up by their path. Matching entries only get their `apps` updated (keeping
+/// any known app sizes) while new libraries get appended with the same keys that Steam uses.
+/// Every other entry and key is kept with its value, but keys are written back in sorted order
+/// rather than their original order since the parsed [`Obj`] is a sorted map
+///
+/// The new contents are written to a sibling temporary file that's then renamed over `path`, so
+/// a failed write never leaves behind a truncated `libraryfolders.vdf`
+pub(crate) fn write_library_folders(path: &Path, libraries: &[Library]) -> Result<()> {
+    let parse_error = |err| Error::parse(ParseErrorKind::LibraryFolders, err, path);
+
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(Error::io(err, path)),
+    };
+    let mut vdf = match &contents {
+        Some(contents) => {
+            Vdf::parse(contents).map_err(|err| parse_error(ParseError::from_parser(err)))?
+        }
+        None => Vdf::new("libraryfolders".into(), Value::Obj(Obj::new())),
+    };
+    let folders = vdf
+        .value
+        .get_mut_obj()
+        .ok_or_else(|| parse_error(ParseError::unexpected_structure()))?;
+
+    for library in libraries {
+        let existing = folders
+            .iter_mut()
+            .filter(|(key, _)| key.parse::<u32>().is_ok())
+            .find_map(|(_, values)| {
+                let folder = values.first_mut()?.get_mut_obj()?;
+                let folder_path = folder
+                    .get("path")
+                    .and_then(|values| values.first())
+                    .and_then(|value| value.get_str())?;
+                (Path::new(folder_path) == library.path()).then_some(folder)
+            });
+
+        match existing {
+            Some(folder) => set_folder_apps(folder, library),
+            None => {
+                let index = folders
+                    .keys()
+                    .filter_map(|key| key.parse::<u32>().ok())
+                    .max()
+                    .map_or(0, |index| index + 1);
+                // Steam identifies libraries by a random content ID. It only has to be unlikely to
+                // collide with the other libraries on this machine, not unpredictable, so the
+                // randomly seeded keys of a fresh `RandomState` are plenty and save pulling in an
+                // RNG. The top bit is dropped so that it also fits in a signed 64-bit integer
+                let content_id = RandomState::new().build_hasher().finish() >> 1;
+
+                let mut folder = Obj::new();
+                let path = library.path().to_string_lossy().into_owned();
+                for (key, value) in [
+                    ("path", path),
+                    ("label", String::new()),
+                    ("contentid", content_id.to_string()),
+                    ("totalsize", "0".to_owned()),
+                    ("update_clean_bytes_tally", "0".to_owned()),
+                    ("time_last_update_corruption", "0".to_owned()),
+                ] {
+                    folder.insert(key.into(), vec![Value::Str(value.into())]);
+                }
+                set_folder_apps(&mut folder, library);
+                folders.insert(index.to_string().into(), vec![Value::Obj(folder)]);
+            }
+        }
+    }
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+    fs::write(&temp_path, vdf.to_string()).map_err(|io| Error::io(io, &temp_path))?;
+    fs::rename(&temp_path, path).map_err(|io| {
+        let _ = fs::remove_file(&temp_path);
+        Error::io(io, path)
+    })
+}
+
+/// Replaces a `libraryfolders.vdf` entry's `apps` while keeping the sizes of any known apps
+///
+/// Apps that were listed without a manifest stay listed, so that reading a library and writing it
+/// back doesn't lose anything
+fn set_folder_apps(folder: &mut Obj<'_>, library: &Library) {
+    let known_sizes: BTreeMap<u32, String> = folder
+        .get("apps")
+        .and_then(|values| values.first())
+        .and_then(|value| value.get_obj())
+        .map(|apps| {
+            apps.iter()
+                .filter_map(|(app_id, sizes)| {
+                    let size = sizes.first()?.get_str()?;
+                    Some((app_id.parse().ok()?, size.to_owned()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut apps = Obj::new();
+    for app_id in library
+        .app_ids()
+        .iter()
+        .chain(library.listed_without_manifest())
+    {
+        let size = known_sizes
+            .get(app_id)
+            .cloned()
+            .unwrap_or_else(|| "0".to_owned());
+        apps.insert(app_id.to_string().into(), vec![Value::Str(size.into())]);
+    }
+    folder.insert("apps".into(), vec![Value::Obj(apps)]);
+}
+
+/// A single library's entry from `libraryfolders.vdf`
+#[derive(Clone, Debug)]
+pub(crate) struct LibraryFolder {
+    pub(crate) path: PathBuf,
+    /// The apps that Steam has listed for this library. This can be stale
+    pub(crate) app_ids: Vec<u32>,
+}
+
+/// Detects paths that are obviously meant for Windows when we're not running on Windows
+///
+/// This checks for either a drive letter prefix (e.g. `C:\`) or any backslashes
+fn is_foreign_windows_path(path: &Path) -> bool {
+    if cfg!(windows) {
+        return false;
+    }
+
+    let path = path.to_string_lossy();
+    let has_drive_prefix = matches!(
+        path.as_bytes(),
+        [drive, b':', b'\\' | b'/', ..] if drive.is_ascii_alphabetic()
+    );
+    has_drive_prefix || path.contains('\\')
+}
+
+/// Strips a spurious verbatim (`\\?\`) prefix from Windows paths
+///
+/// Paths handed out by the crate never carry the prefix when they can be represented without
+/// it, so that they compare equal regardless of how they were obtained. Rust's standard library
+/// adds the prefix back internally where it's needed for long paths, so nothing is lost by
+/// stripping it. This is a no-op on other platforms
+pub(crate) fn normalize_path(path: PathBuf) -> PathBuf {
+    if cfg!(windows) {
+        if let Some(stripped) = path.to_str().and_then(strip_verbatim_prefix) {
+            return PathBuf::from(stripped);
+        }
+    }
 
-    Ok(paths)
+    path
+}
+
+/// Returns `path` without its verbatim prefix when that doesn't change its meaning
+///
+/// Only drive paths (`\\?\C:\...`) and UNC paths (`\\?\UNC\server\share\...`) are handled.
+/// Verbatim paths skip Windows' usual path normalization, so they're kept as-is when they include
+/// anything that normalization would change (e.g. forward slashes, `..` components, trailing
+/// dots or spaces, or reserved device names like `NUL`)
+pub(crate) fn strip_verbatim_prefix(path: &str) -> Option<String> {
+    let verbatim = path.strip_prefix(r"\\?\")?;
+    let (stripped, rest) = if let Some(unc) = verbatim.strip_prefix(r"UNC\") {
+        (format!(r"\\{unc}"), unc)
+    } else {
+        let rest = match verbatim.as_bytes() {
+            [drive, b':', b'\\', ..] if drive.is_ascii_alphabetic() => &verbatim[3..],
+            _ => return None,
+        };
+        (verbatim.to_owned(), rest)
+    };
+
+    let is_reserved = |component: &str| {
+        let stem = component.split('.').next().unwrap_or_default();
+        let stem = stem.trim_end().to_ascii_uppercase();
+        matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+            || (stem.len() == 4
+                && (stem.starts_with("COM") || stem.starts_with("LPT"))
+                && stem.as_bytes()[3].is_ascii_digit())
+    };
+    let representable = !rest.contains('/')
+        && rest
+            .split('\\')
+            .filter(|component| !component.is_empty())
+            .all(|component| !component.ends_with(['.', ' ']) && !is_reserved(component));
+    representable.then_some(stripped)
+}
+
+/// Lists the IDs of all the apps with a manifest in the library at `path`
+///
+/// When `verify_files` is set, each entry with a manifest's name is also checked to be a file.
+/// That check uses the file type from the directory listing, so it only costs a metadata call for
+/// symlinks. Other entries are only ever looked at by name
+fn scan_app_ids(path: &Path, verify_files: bool) -> Result<Vec<u32>> {
+    // Read the manifest files at the library to get an up-to-date list of apps since the
+    // values in `libraryfolders.vdf` may be stale. Backup manifests are included too since
+    // Steam can briefly remove the primary manifest while it's writing a new one
+    let mut apps = Vec::new();
+    let steamapps = path.join("steamapps");
+    let entries = fs::read_dir(&steamapps).map_err(|io| {
+        // A path copied over from a Windows install will never exist, so call that out
+        // specifically instead of giving a vague I/O error
+        if is_foreign_windows_path(path) {
+            Error::validation(ValidationError::foreign_path(path))
+        } else {
+            Error::read_dir(io, &steamapps)
+        }
+    })?;
+    for entry in entries {
+        let entry = entry.map_err(|io| Error::read_dir(io, &steamapps))?;
+        let Some(id) = manifest_app_id(&entry.file_name()) else {
+            continue;
+        };
+        if !verify_files || is_file(&entry) {
+            apps.push(id);
+        }
+    }
+
+    // `read_dir()`'s order depends on the filesystem, so sort to keep things deterministic
+    apps.sort_unstable();
+    apps.dedup();
+    trace::debug!(
+        "found {} app manifests in {}",
+        apps.len(),
+        steamapps.display(),
+    );
+    Ok(apps)
+}
+
+/// Checks if `entry` is a file, following it if it's a symlink
+///
+/// The entry's file type usually comes along with listing the directory, so only symlinks cost an
+/// extra metadata call
+fn is_file(entry: &fs::DirEntry) -> bool {
+    match entry.file_type() {
+        Ok(file_type) if file_type.is_symlink() => {
+            fs::metadata(entry.path()).is_ok_and(|meta| meta.is_file())
+        }
+        Ok(file_type) => file_type.is_file(),
+        Err(_) => false,
+    }
+}
+
+/// Parses the app ID from an `appmanifest_<APP_ID>.acf` (or `.acf.bak`) file name
+fn manifest_app_id(file_name: &OsStr) -> Option<u32> {
+    const PREFIX: &str = "appmanifest_";
+
+    // Most entries are things like `common`, `downloading`, and `workshop`, so rule them out from
+    // the raw bytes before bothering with any UTF-8 validation
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        if !file_name.as_bytes().starts_with(PREFIX.as_bytes()) {
+            return None;
+        }
+    }
+
+    let prefixless_name = file_name.to_str()?.strip_prefix(PREFIX)?;
+    prefixless_name
+        .strip_suffix(".acf")
+        .or_else(|| prefixless_name.strip_suffix(".acf.bak"))?
+        .parse()
+        .ok()
 }
 
 /// An [`Iterator`] over a Steam installation's [`Library`]s
 ///
 /// Returned from calling [`SteamDir::libraries()`][super::SteamDir::libraries]
 pub struct Iter {
-    paths: std::vec::IntoIter<PathBuf>,
+    folders: std::vec::IntoIter<LibraryFolder>,
 }
 
 impl Iter {
-    pub(crate) fn new(paths: Vec<PathBuf>) -> Self {
+    pub(crate) fn new(folders: Vec<LibraryFolder>) -> Self {
         Self {
-            paths: paths.into_iter(),
+            folders: folders.into_iter(),
         }
     }
 }
@@ -99,13 +389,19 @@ impl Iterator for Iter {
     type Item = Result<Library>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.paths.next().map(|path| Library::from_dir(&path))
+        self.folders.next().map(Library::from_folder)
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.folders.next_back().map(Library::from_folder)
     }
 }
 
 impl ExactSizeIterator for Iter {
     fn len(&self) -> usize {
-        self.paths.len()
+        self.folders.len()
     }
 }
 
@@ -114,6 +410,8 @@ impl ExactSizeIterator for Iter {
 pub struct Library {
     path: PathBuf,
     apps: Vec<u32>,
+    /// Apps that `libraryfolders.vdf` lists for this library, but have no manifest on disk
+    listed: Vec<u32>,
 }
 
 impl Library {
@@ -122,30 +420,144 @@ impl Library {
     /// You'll typically want to use methods that handle locating the library for you like
     /// [`SteamDir::libraries()`][super::SteamDir::libraries] or
     /// [`SteamDir::find_app()`][super::SteamDir::find_app].
+    ///
+    /// This only lists the library's `steamapps` directory once and never recurses into any
+    /// subdirectories (like `common` or `workshop`). Entries are filtered by name first, and only
+    /// the ones named like an app manifest are checked to actually be a file. That check uses the
+    /// file type from the directory listing where the platform provides it, so only symlinked
+    /// manifests need their own metadata call. Use [`Library::from_dir_shallow()`] to skip that
+    /// check too
+    ///
+    /// Returns [`Error::PermissionDenied`] if the library's `steamapps` folder can't be read due
+    /// to its permissions
     pub fn from_dir(path: &Path) -> Result<Self> {
-        // Read the manifest files at the library to get an up-to-date list of apps since the
-        // values in `libraryfolders.vdf` may be stale
-        let mut apps = Vec::new();
-        let steamapps = path.join("steamapps");
-        for entry in fs::read_dir(&steamapps).map_err(|io| Error::io(io, &steamapps))? {
-            let entry = entry.map_err(|io| Error::io(io, &steamapps))?;
-            if let Some(id) = entry
-                .file_name()
-                .to_str()
-                .and_then(|name| name.strip_prefix("appmanifest_"))
-                .and_then(|prefixless_name| prefixless_name.strip_suffix(".acf"))
-                .and_then(|app_id_str| app_id_str.parse().ok())
-            {
-                apps.push(id);
-            }
-        }
+        let apps = scan_app_ids(path, true)?;
+        Ok(Self {
+            path: path.to_owned(),
+            apps,
+            listed: Vec::new(),
+        })
+    }
 
+    /// Like [`Library::from_dir()`], but apps are listed from the names in `steamapps` alone
+    ///
+    /// No metadata is read for any entry (not even the app manifests), which keeps this cheap on
+    /// slow filesystems like network mounts where each metadata call is a round trip. Since the
+    /// manifests aren't checked, something like a directory named `appmanifest_<APP_ID>.acf` is
+    /// treated as an app and only reported as an error once [`Library::app()`] tries to read it.
+    /// This is also how libraries from [`SteamDir::libraries()`][super::SteamDir::libraries] are
+    /// read
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # let library_path = steam_dir.path();
+    /// use steamlocate::Library;
+    ///
+    /// # /*
+    /// let library_path = /* Somehow get a library's path */;
+    /// # */
+    /// let library = Library::from_dir_shallow(library_path)?;
+    /// for app_id in library.app_ids() {
+    ///     println!("Found app {app_id}");
+    /// }
+    /// # assert_eq!(library.app_ids(), [4_000, 230_410]);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn from_dir_shallow(path: &Path) -> Result<Self> {
+        let apps = scan_app_ids(path, false)?;
         Ok(Self {
             path: path.to_owned(),
             apps,
+            listed: Vec::new(),
         })
     }
 
+    /// Creates a [`Library`] from its `libraryfolders.vdf` entry
+    ///
+    /// The listed apps that are missing a manifest on disk are kept separately, so that apps that
+    /// Steam expects to be installed get reported as [`Error::MissingExpectedApp`] instead of
+    /// silently being skipped
+    fn from_folder(folder: LibraryFolder) -> Result<Self> {
+        let LibraryFolder { path, app_ids } = folder;
+        // Stick to names since checking each manifest adds up across many libraries
+        let apps = scan_app_ids(&path, false)?;
+        let mut listed: Vec<_> = app_ids
+            .into_iter()
+            .filter(|app_id| apps.binary_search(app_id).is_err())
+            .collect();
+        listed.sort_unstable();
+        listed.dedup();
+
+        Ok(Self { path, apps, listed })
+    }
+
+    /// Creates a [`Library`] from its installation directory and an already known list of apps
+    ///
+    /// Unlike [`Library::from_dir()`] this doesn't perform any I/O, which makes it useful when
+    /// you've already listed the library's apps through some other means. Note that `app_ids` is
+    /// trusted as-is (other than being sorted), so it may be stale compared to what's actually
+    /// installed in the library
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::Library;
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # let library_path = steam_dir.path();
+    /// const GMOD: u32 = 4_000;
+    /// # /*
+    /// let library_path = /* Somehow get a library's path */;
+    /// # */
+    /// let library = Library::from_dir_with_apps(library_path, vec![GMOD]);
+    /// assert_eq!(library.app_ids(), [GMOD]);
+    /// let gmod = library.app(GMOD).unwrap()?;
+    /// # assert_eq!(gmod.app_id, GMOD);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn from_dir_with_apps(path: &Path, mut app_ids: Vec<u32>) -> Self {
+        app_ids.sort_unstable();
+        app_ids.dedup();
+        Self {
+            path: path.to_owned(),
+            apps: app_ids,
+            listed: Vec::new(),
+        }
+    }
+
+    /// Returns a copy of this library that's based at `path` instead
+    ///
+    /// The app list is kept as-is and nothing is read from disk, so this is handy for working out
+    /// where things would end up after moving a library
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::PathBuf;
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// let moved = library.with_path(PathBuf::from("/mnt/games/SteamLibrary"));
+    /// assert_eq!(moved.app_ids(), library.app_ids());
+    /// let new_gmod_dir = moved.resolve_app_dir(&gmod);
+    /// assert!(new_gmod_dir.starts_with("/mnt/games/SteamLibrary"));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn with_path(&self, path: PathBuf) -> Library {
+        Self {
+            path,
+            apps: self.apps.clone(),
+            listed: self.listed.clone(),
+        }
+    }
+
     /// Returns the path to the library's installation directory
     ///
     /// # Example
@@ -165,16 +577,128 @@ impl Library {
         &self.path
     }
 
+    /// The library's `steamapps` directory
+    ///
+    /// This holds the app manifests along with most of the other per-library data (downloads,
+    /// shader caches, Proton prefixes, etc.)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let library = steam_dir.libraries()?.next().unwrap()?;
+    /// let library_folders = library.steamapps_path().join("libraryfolders.vdf");
+    /// # assert!(library_folders.is_file());
+    /// # assert!(library.common_path().starts_with(library.steamapps_path()));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn steamapps_path(&self) -> PathBuf {
+        self.path.join("steamapps")
+    }
+
+    /// The library's `steamapps/common` directory that apps get installed into
+    ///
+    /// _See [`Library::resolve_app_dir()`] for a specific app's installation directory_
+    pub fn common_path(&self) -> PathBuf {
+        self.steamapps_path().join("common")
+    }
+
     /// Returns the full list of Application IDs located within this library
+    ///
+    /// These are the apps with a manifest in `steamapps`. The IDs are sorted in ascending order,
+    /// so repeated scans of an unchanged library always give the same result
+    ///
+    /// _See [`Library::listed_without_manifest()`] for apps that Steam expects to be here, but
+    /// are missing their manifest_
     pub fn app_ids(&self) -> &[u32] {
         &self.apps
     }
 
+    /// Returns the IDs of apps that `libraryfolders.vdf` lists for this library, but that have no
+    /// manifest on disk
+    ///
+    /// Steam can keep listing an app after its manifest is gone (e.g. an interrupted uninstall).
+    /// [`Library::app()`] returns an [`Error::MissingExpectedApp`] for these IDs. Libraries that
+    /// weren't read through `libraryfolders.vdf` (e.g. from [`Library::from_dir()`]) never have
+    /// any. The IDs are sorted in ascending order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for library in steam_dir.libraries()? {
+    ///     let library = library?;
+    ///     for app_id in library.listed_without_manifest() {
+    ///         println!("{app_id} is missing from {}", library.path().display());
+    ///     }
+    /// #   assert!(library.listed_without_manifest().is_empty());
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn listed_without_manifest(&self) -> &[u32] {
+        &self.listed
+    }
+
+    /// Compares the apps in this library against a `previous` snapshot of it
+    ///
+    /// This is pure set math over the [`app_ids()`][Self::app_ids] of both libraries, so no extra
+    /// I/O is performed. It's useful for change detection when combined with periodically
+    /// re-reading a library
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::Library;
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # let library = steam_dir.libraries()?.next().unwrap()?;
+    /// # /*
+    /// let library = /* Somehow get a library */;
+    /// # */
+    /// let previous = library.clone();
+    /// // ... some time later
+    /// let current = Library::from_dir(library.path())?;
+    /// let diff = current.diff(&previous);
+    /// for app_id in &diff.added {
+    ///     println!("{app_id} was installed");
+    /// }
+    /// for app_id in &diff.removed {
+    ///     println!("{app_id} was uninstalled");
+    /// }
+    /// # assert!(diff.is_empty());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn diff(&self, previous: &Library) -> LibraryDiff {
+        let current: BTreeSet<_> = self.app_ids().iter().copied().collect();
+        let previous: BTreeSet<_> = previous.app_ids().iter().copied().collect();
+
+        LibraryDiff {
+            added: current.difference(&previous).copied().collect(),
+            removed: previous.difference(&current).copied().collect(),
+        }
+    }
+
     /// Attempts to return the [`App`] identified by `app_id`
     ///
     /// Returns [`None`] if the app isn't located within this library. Otherwise it attempts to
     /// return metadata for the installed app
     ///
+    /// If the app's manifest is missing, but a backup of it (`appmanifest_<APP_ID>.acf.bak`)
+    /// exists then the backup is read instead and [`App::from_backup`] is set. If neither exist
+    /// (e.g. the app is listed in `libraryfolders.vdf`, but its manifest is gone) then an
+    /// [`Error::MissingExpectedApp`] is returned
+    ///
     /// # Example
     ///
     /// ```
@@ -192,15 +716,109 @@ impl Library {
     /// # Ok::<_, TestError>(())
     /// ```
     pub fn app(&self, app_id: u32) -> Option<Result<App>> {
+        if self.listed_without_manifest().contains(&app_id) {
+            return Some(Err(Error::MissingExpectedApp { app_id }));
+        }
+
         self.app_ids().iter().find(|&&id| id == app_id).map(|&id| {
-            let manifest_path = self
-                .path()
-                .join("steamapps")
-                .join(format!("appmanifest_{}.acf", id));
-            App::new(&manifest_path)
+            let manifest_path = self.manifest_path(id);
+            let mut backup_path = manifest_path.clone().into_os_string();
+            backup_path.push(".bak");
+            let backup_path = PathBuf::from(backup_path);
+
+            if manifest_path.is_file() {
+                App::new(&manifest_path)
+            } else if backup_path.is_file() {
+                let mut app = App::new(&backup_path)?;
+                app.from_backup = true;
+                Ok(app)
+            } else {
+                // The manifest was removed after the library was read
+                Err(Error::MissingExpectedApp { app_id })
+            }
+        })
+    }
+
+    /// Like [`Library::app()`] except that apps missing their manifest are treated as not being
+    /// in this library at all
+    pub(crate) fn installed_app(&self, app_id: u32) -> Option<Result<App>> {
+        match self.app(app_id)? {
+            Err(Error::MissingExpectedApp { .. }) => None,
+            maybe_app => Some(maybe_app),
+        }
+    }
+
+    /// Attempts to return the [`App`] whose [`install_dir`][App::install_dir] is `dir_name`
+    ///
+    /// This is the reverse of [`Library::resolve_app_dir()`] and is handy for figuring out which
+    /// app a folder in `steamapps/common` belongs to. Manifests are parsed until a match is found,
+    /// so this is more expensive than [`Library::app()`]. The comparison ignores case on Windows
+    /// and macOS since their filesystems are typically case-insensitive
+    ///
+    /// Returns [`None`] if no app in the library uses `dir_name`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # let library = steam_dir.libraries()?.next().unwrap()?;
+    /// # /*
+    /// let library = /* Somehow get a library */;
+    /// # */
+    /// let gmod = library.app_by_install_dir("GarrysMod").unwrap()?;
+    /// assert_eq!(gmod.app_id, 4_000);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn app_by_install_dir(&self, dir_name: &str) -> Option<Result<App>> {
+        let matches = |install_dir: &str| {
+            if cfg!(any(windows, target_os = "macos")) {
+                install_dir.eq_ignore_ascii_case(dir_name)
+            } else {
+                install_dir == dir_name
+            }
+        };
+
+        self.apps().find(|maybe_app| match maybe_app {
+            Ok(app) => matches(&app.install_dir),
+            Err(_) => false,
         })
     }
 
+    /// Returns when the `appmanifest_<APP_ID>.acf` file for `app_id` was last modified
+    ///
+    /// This differs from [`App::last_updated`] which tracks when Steam last updated the app's
+    /// content. The manifest can also be touched by Steam for other reasons, like after verifying
+    /// the app's files
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # let library = steam_dir.libraries()?.next().unwrap()?;
+    /// const GMOD: u32 = 4_000;
+    /// # /*
+    /// let library = /* Somehow get a library */;
+    /// # */
+    /// let modified = library.manifest_modified(GMOD)?;
+    /// println!("Steam last touched the manifest at {modified:?}");
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn manifest_modified(&self, app_id: u32) -> Result<SystemTime> {
+        let manifest_path = self.manifest_path(app_id);
+        fs::metadata(&manifest_path)
+            .and_then(|meta| meta.modified())
+            .map_err(|io| Error::io(io, &manifest_path))
+    }
+
+    fn manifest_path(&self, app_id: u32) -> PathBuf {
+        self.steamapps_path()
+            .join(format!("appmanifest_{}.acf", app_id))
+    }
+
     /// Returns an [`Iterator`] over all of the [`App`]s contained in this library
     ///
     /// # Example
@@ -225,10 +843,42 @@ impl Library {
     /// # assert_eq!(total_size, 30804429728);
     /// # Ok::<_, TestError>(())
     /// ```
-    pub fn apps(&self) -> app::Iter {
+    pub fn apps(&self) -> app::Iter<'_> {
         app::Iter::new(self)
     }
 
+    /// Like [`Library::apps()`], but the scan can be stopped early through `cancel`
+    ///
+    /// `cancel` is checked before reading each app's manifest. Once it's set the iterator returns
+    /// an [`Error::Cancelled`] and then ends
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # let library = steam_dir.libraries()?.next().unwrap()?;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use steamlocate::Error;
+    ///
+    /// # /*
+    /// let library = /* Somehow get a library */;
+    /// # */
+    /// let cancel = AtomicBool::new(false);
+    /// cancel.store(true, Ordering::Relaxed);
+    /// let mut apps = library.apps_cancellable(&cancel);
+    /// assert!(matches!(apps.next(), Some(Err(Error::Cancelled))));
+    /// assert!(apps.next().is_none());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn apps_cancellable<'cancel>(
+        &self,
+        cancel: &'cancel AtomicBool,
+    ) -> Cancellable<'cancel, app::Iter<'_>> {
+        Cancellable::new(self.apps(), cancel)
+    }
+
     /// Resolves the theoretical installation directory for the given `app`
     ///
     /// This is an unvalidated path, so it's up to you to call this with an `app` that's in this
@@ -249,9 +899,438 @@ impl Library {
     /// # Ok::<_, TestError>(())
     /// ```
     pub fn resolve_app_dir(&self, app: &App) -> PathBuf {
-        self.path
-            .join("steamapps")
-            .join("common")
-            .join(&app.install_dir)
+        self.common_path().join(&app.install_dir)
+    }
+
+    /// The directory that Steam stages downloaded content in before it's moved into place
+    ///
+    /// This is `steamapps/downloading`. Content only moves into
+    /// [`resolve_app_dir()`][Self::resolve_app_dir] once staging finishes, so this is the place to
+    /// watch for disk usage during a large update. The directory may not exist when nothing is
+    /// downloading
+    pub fn staging_dir(&self) -> PathBuf {
+        self.steamapps_path().join("downloading")
+    }
+
+    /// The directory that Steam stages the in-progress download for `app_id` in
+    ///
+    /// This is `steamapps/downloading/<APP_ID>`. Like [`Library::staging_dir()`] this is
+    /// unvalidated. Compare with [`App::bytes_staged`] and [`App::staging_size`] to track progress
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const WARFRAME: u32 = 230_410;
+    /// let (_warframe, library) = steam_dir.find_app(WARFRAME)?.unwrap();
+    /// let staging_dir = library.app_staging_dir(WARFRAME);
+    /// if staging_dir.is_dir() {
+    ///     println!("Warframe is being downloaded into {}", staging_dir.display());
+    /// }
+    /// # let expected_rel_path = Path::new("steamapps").join("downloading").join("230410");
+    /// # assert!(staging_dir.ends_with(expected_rel_path));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn app_staging_dir(&self, app_id: u32) -> PathBuf {
+        self.staging_dir().join(app_id.to_string())
+    }
+
+    /// Lists the directories in [`common_path()`][Self::common_path] that don't belong to any
+    /// installed app
+    ///
+    /// These are typically left behind by an uninstall that didn't finish cleaning up. Each
+    /// installed app's manifest is parsed to get its [`install_dir`][App::install_dir], and any
+    /// manifest that fails to parse is returned as an error instead of risking reporting its
+    /// directory as orphaned. Apps that are missing their manifest aren't considered installed.
+    /// Install dirs are matched case-insensitively on Windows. The paths are sorted
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for library in steam_dir.libraries()? {
+    ///     for orphan in library?.orphaned_dirs()? {
+    ///         println!("{} isn't used by any installed app", orphan.display());
+    ///     }
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn orphaned_dirs(&self) -> Result<Vec<PathBuf>> {
+        let normalize = |name: &OsStr| {
+            if cfg!(windows) {
+                name.to_ascii_lowercase()
+            } else {
+                name.to_owned()
+            }
+        };
+
+        let mut install_dirs = HashSet::new();
+        for &app_id in self.app_ids() {
+            if let Some(app) = self.installed_app(app_id) {
+                install_dirs.insert(normalize(OsStr::new(&app?.install_dir)));
+            }
+        }
+
+        let common = self.common_path();
+        let entries = match fs::read_dir(&common) {
+            Ok(entries) => entries,
+            // Nothing can be orphaned when nothing was ever installed
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(Error::read_dir(err, &common)),
+        };
+        let mut orphans = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|io| Error::read_dir(io, &common))?;
+            let path = entry.path();
+            if path.is_dir() && !install_dirs.contains(&normalize(&entry.file_name())) {
+                orphans.push(path);
+            }
+        }
+
+        orphans.sort_unstable();
+        Ok(orphans)
+    }
+
+    /// Measures the actual size of `app`'s install directory by summing the sizes of all of its
+    /// files
+    ///
+    /// This can differ from [`App::size_on_disk`], which is only what Steam has recorded (e.g.
+    /// after a failed update or manually changing files). Walking the whole directory is
+    /// expensive, so this is never done implicitly
+    ///
+    /// Symlinks are counted by the size of their target. Each target is only counted once and
+    /// symlinked directories are never walked more than once, so symlink loops are fine
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// let measured = library.measured_app_size(&gmod)?;
+    /// if Some(measured) != gmod.size_on_disk {
+    ///     println!("Steam thinks Garry's Mod is {:?} bytes, but it's {measured}", gmod.size_on_disk);
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn measured_app_size(&self, app: &App) -> Result<u64> {
+        let app_dir = self.resolve_app_dir(app);
+        let root = fs::canonicalize(&app_dir).map_err(|io| Error::io(io, &app_dir))?;
+
+        // Every directory walked is canonical, so the paths of the entries within it are too
+        let mut visited = HashSet::new();
+        visited.insert(root.clone());
+        let mut pending = vec![root];
+        let mut size = 0;
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(&dir).map_err(|io| Error::io(io, &dir))? {
+                let entry = entry.map_err(|io| Error::io(io, &dir))?;
+                let mut path = entry.path();
+                let mut meta = entry.metadata().map_err(|io| Error::io(io, &path))?;
+                if meta.file_type().is_symlink() {
+                    // Count the target instead, skipping any dangling links
+                    let target = fs::canonicalize(&path)
+                        .and_then(|target| Ok((fs::metadata(&target)?, target)));
+                    let Ok((target_meta, target)) = target else {
+                        continue;
+                    };
+                    path = target;
+                    meta = target_meta;
+                }
+
+                if !visited.insert(path.clone()) {
+                    continue;
+                }
+                if meta.is_dir() {
+                    pending.push(path);
+                } else {
+                    size += meta.len();
+                }
+            }
+        }
+
+        Ok(size)
+    }
+
+    /// Whether `app`'s files are on the same volume (filesystem) as the library itself
+    ///
+    /// The app's install directory is resolved with [`Library::resolve_app_dir()`] with any
+    /// symlinks followed, so an install dir that's symlinked to another drive is detected. This
+    /// is useful for deciding whether an app can be cheaply moved (renamed) or has to be copied
+    ///
+    /// On Unix this compares the device IDs. Elsewhere the path prefixes (e.g. drive letters) are
+    /// compared instead, which won't detect volumes that are mounted into a folder
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// if library.app_on_same_volume(&gmod)? {
+    ///     println!("Garry's Mod can be moved within its library without copying");
+    /// }
+    /// # assert!(library.app_on_same_volume(&gmod)?);
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn app_on_same_volume(&self, app: &App) -> Result<bool> {
+        let app_dir = self.resolve_app_dir(app);
+        let app_dir = fs::canonicalize(&app_dir).map_err(|io| Error::io(io, &app_dir))?;
+        let library_dir = fs::canonicalize(&self.path).map_err(|io| Error::io(io, &self.path))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            let device_id = |path: &Path| {
+                fs::metadata(path)
+                    .map(|meta| meta.dev())
+                    .map_err(|io| Error::io(io, path))
+            };
+            Ok(device_id(&app_dir)? == device_id(&library_dir)?)
+        }
+        #[cfg(not(unix))]
+        {
+            // Volume serial numbers aren't available on stable, so compare the drive or share
+            // prefix instead
+            Ok(app_dir.components().next() == library_dir.components().next())
+        }
+    }
+
+    /// Returns the auxiliary directories that Steam keeps for `app_id` outside of its install dir
+    ///
+    /// Each directory is only included if it exists
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (_gmod, library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// let aux_dirs = library.auxiliary_dirs(GMOD);
+    /// if let Some(shader_cache) = aux_dirs.shader_cache {
+    ///     println!("Shaders are cached in {}", shader_cache.display());
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn auxiliary_dirs(&self, app_id: u32) -> AuxDirs {
+        let steamapps = self.steamapps_path();
+        let app_id = app_id.to_string();
+        let existing = |path: PathBuf| path.is_dir().then_some(path);
+
+        AuxDirs {
+            shader_cache: existing(steamapps.join("shadercache").join(&app_id)),
+            compat_data: existing(steamapps.join("compatdata").join(&app_id)),
+            workshop_content: existing(steamapps.join("workshop").join("content").join(&app_id)),
+        }
+    }
+
+    /// Builds a command that reveals the library's directory in a file manager
+    ///
+    /// _See [`reveal::reveal_command()`] for more info_
+    pub fn reveal_command(&self) -> Command {
+        reveal::reveal_command(&self.path)
+    }
+
+    /// Builds a command that reveals `app`'s install directory in a file manager
+    ///
+    /// The directory is resolved with [`Library::resolve_app_dir()`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (gmod, library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// let command = library.reveal_app_command(&gmod);
+    /// # assert!(command.get_args().any(|arg| arg == library.resolve_app_dir(&gmod)));
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn reveal_app_command(&self, app: &App) -> Command {
+        reveal::reveal_command(&self.resolve_app_dir(app))
+    }
+
+    /// Works out what manually uninstalling the app identified by `app_id` would involve
+    ///
+    /// Nothing gets deleted. The plan includes the app's install directory and manifest along
+    /// with which of its depots are only used by it and which other installed apps in this
+    /// library depend on its depots. Deleting an app that other apps depend on can break them, so
+    /// check [`UninstallPlan::shared_with`] before removing anything
+    ///
+    /// Returns an [`Error::MissingExpectedApp`] if the app isn't installed in this library
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir = expect_test_env();
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// const GMOD: u32 = 4_000;
+    /// let (_gmod, library) = steam_dir.find_app(GMOD)?.unwrap();
+    /// let plan = library.uninstall_plan(GMOD)?;
+    /// println!("Uninstalling would free up {} bytes", plan.freed_size());
+    /// if !plan.shared_with.is_empty() {
+    ///     println!("Warning: {:?} depend on its content", plan.shared_with);
+    /// }
+    /// # assert!(plan.shared_with.is_empty());
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn uninstall_plan(&self, app_id: u32) -> Result<UninstallPlan> {
+        let app = self
+            .app(app_id)
+            .unwrap_or(Err(Error::MissingExpectedApp { app_id }))?;
+        let mut manifest_path = self.manifest_path(app_id);
+        if app.from_backup {
+            manifest_path.as_mut_os_string().push(".bak");
+        }
+
+        let mut owned_depots: BTreeMap<_, _> = app
+            .installed_depots
+            .iter()
+            .map(|(&depot_id, depot)| (depot_id, depot.size))
+            .collect();
+        let mut shared_with = Vec::new();
+        for maybe_other in self.apps() {
+            let other = match maybe_other {
+                Ok(other) if other.app_id != app_id => other,
+                Ok(_) | Err(Error::MissingExpectedApp { .. }) => continue,
+                Err(err) => return Err(err),
+            };
+
+            let used_depots: Vec<_> = other
+                .shared_depots
+                .keys()
+                .chain(other.installed_depots.keys())
+                .filter(|depot_id| app.installed_depots.contains_key(depot_id))
+                .collect();
+            let depends_on_app = other
+                .shared_depots
+                .values()
+                .any(|&owner| owner == u64::from(app_id));
+            if depends_on_app || !used_depots.is_empty() {
+                shared_with.push(other.app_id);
+            }
+            for depot_id in used_depots {
+                owned_depots.remove(depot_id);
+            }
+        }
+        shared_with.sort_unstable();
+
+        Ok(UninstallPlan {
+            app_id,
+            install_dir: self.resolve_app_dir(&app),
+            manifest_path,
+            owned_depots,
+            shared_with,
+        })
+    }
+}
+
+/// An analysis of what manually uninstalling an app would involve
+///
+/// Returned from calling [`Library::uninstall_plan()`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UninstallPlan {
+    /// The ID of the app that would be uninstalled
+    pub app_id: u32,
+    /// The app's install directory
+    pub install_dir: PathBuf,
+    /// The path to the app's manifest file
+    pub manifest_path: PathBuf,
+    /// The size of each installed depot that isn't used by any other installed app
+    pub owned_depots: BTreeMap<u64, u64>,
+    /// IDs of other installed apps in the library that use any of the app's depots
+    ///
+    /// Sorted in ascending order
+    pub shared_with: Vec<u32>,
+}
+
+impl UninstallPlan {
+    /// The total size of the [owned depots][Self::owned_depots]
+    pub fn freed_size(&self) -> u64 {
+        self.owned_depots.values().sum()
+    }
+}
+
+/// The auxiliary directories that Steam keeps for an app within a [`Library`]
+///
+/// Returned from calling [`Library::auxiliary_dirs()`]. Each directory is [`None`] when it
+/// doesn't exist
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AuxDirs {
+    /// Compiled shaders at `steamapps/shadercache/<APP_ID>`
+    pub shader_cache: Option<PathBuf>,
+    /// The Proton prefix and other compatibility data at `steamapps/compatdata/<APP_ID>`
+    pub compat_data: Option<PathBuf>,
+    /// Downloaded workshop items at `steamapps/workshop/content/<APP_ID>`
+    pub workshop_content: Option<PathBuf>,
+}
+
+impl AuxDirs {
+    /// Returns an [`Iterator`] over all of the directories that exist
+    pub fn iter(&self) -> impl Iterator<Item = &Path> {
+        [
+            &self.shader_cache,
+            &self.compat_data,
+            &self.workshop_content,
+        ]
+        .into_iter()
+        .flatten()
+        .map(PathBuf::as_path)
+    }
+}
+
+/// The difference in installed apps between two snapshots of a [`Library`]
+///
+/// Returned from calling [`Library::diff()`]. Both lists are sorted in ascending order
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LibraryDiff {
+    /// App IDs that are present now, but weren't previously
+    pub added: Vec<u32>,
+    /// App IDs that were present previously, but aren't now
+    pub removed: Vec<u32>,
+}
+
+impl LibraryDiff {
+    /// Returns `true` if no apps were added or removed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
     }
 }
@@ -1,38 +1,130 @@
-use std::path::PathBuf;
+use std::{env, ffi::OsString, path::PathBuf};
 
-use crate::Result;
+use crate::{trace, Result};
+
+/// How a located Steam installation was installed (or found)
+///
+/// Returned alongside each installation from
+/// [`SteamDir::locate_all_with_types()`][crate::SteamDir::locate_all_with_types]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InstallationType {
+    /// Pointed to by one of the environment variables used by SteamCMD
+    Environment,
+    /// The installation listed in the Windows registry
+    Windows,
+    /// The installation in `~/Library/Application Support/Steam` on macOS
+    MacOs,
+    /// A native Linux installation from a distro package or Valve's installer
+    LinuxNative,
+    /// The Steam Flatpak on Linux
+    LinuxFlatpak,
+    /// The Steam Snap on Linux
+    LinuxSnap,
+    /// Found through a running Steam process on Linux when none of the usual locations exist
+    LinuxRunningProcess,
+}
+
+/// Environment variables used by SteamCMD and dedicated server tooling to point at a Steam
+/// installation, in order of precedence
+const ENV_VARS: &[&str] = &["STEAM_BASE_FOLDER", "STEAM_ROOT"];
 
 pub fn locate_steam_dir() -> Result<PathBuf> {
+    if let Some(path) = steam_dir_from_env(|var| env::var_os(var)) {
+        trace::debug!(
+            "located Steam from an environment variable: {}",
+            path.display()
+        );
+        return Ok(path);
+    }
+
     locate_steam_dir_helper()
 }
 
+/// Lists every Steam installation that can be found along with how it was installed
+///
+/// Installations pointed to by [`ENV_VARS`] come first followed by the platform specific ones.
+/// Only existing directories are included, but the same installation can show up more than once
+/// (e.g. through Linux's `~/.steam/steam` symlink)
+pub fn locate_steam_dirs() -> Result<Vec<(PathBuf, InstallationType)>> {
+    let mut dirs: Vec<_> = ENV_VARS
+        .iter()
+        .filter_map(env::var_os)
+        .map(PathBuf::from)
+        .filter(|path| path.is_dir())
+        .map(|path| (path, InstallationType::Environment))
+        .collect();
+    match locate_steam_dirs_helper() {
+        Ok(platform_dirs) => dirs.extend(platform_dirs),
+        // Still return whatever the environment pointed at
+        Err(_) if !dirs.is_empty() => {}
+        Err(err) => return Err(err),
+    }
+
+    Ok(dirs)
+}
+
+/// Returns the first of [`ENV_VARS`] that's set to an existing directory
+fn steam_dir_from_env(get_var: impl Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    ENV_VARS
+        .iter()
+        .filter_map(|var| get_var(var))
+        .map(PathBuf::from)
+        .find(|path| path.is_dir())
+}
+
 #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
 fn locate_steam_dir_helper() -> Result<PathBuf> {
     use crate::error::{Error, LocateError};
     Err(Error::locate(LocateError::Unsupported))
 }
 
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn locate_steam_dirs_helper() -> Result<Vec<(PathBuf, InstallationType)>> {
+    locate_steam_dir_helper().map(|_| Vec::new())
+}
+
+#[cfg(target_os = "windows")]
+fn locate_steam_dirs_helper() -> Result<Vec<(PathBuf, InstallationType)>> {
+    let path = locate_steam_dir_helper()?;
+    Ok(vec![(path, InstallationType::Windows)])
+}
+
 #[cfg(target_os = "windows")]
 fn locate_steam_dir_helper() -> Result<PathBuf> {
+    use winreg::{enums::HKEY_LOCAL_MACHINE, RegKey};
+
+    // Steam's installation location can be found in the registry
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    locate_steam_dir_from_hive(&hklm)
+}
+
+/// Locates the Steam installation directory from the registry keys stored under `hive`
+///
+/// This is normally `HKEY_LOCAL_MACHINE`, but it's split out so that an alternate hive can be
+/// provided instead (e.g. a sandboxed test hive)
+#[cfg(target_os = "windows")]
+pub(crate) fn locate_steam_dir_from_hive(hive: &winreg::RegKey) -> Result<PathBuf> {
     use crate::error::{Error, LocateError};
 
-    use winreg::{
-        enums::{HKEY_LOCAL_MACHINE, KEY_READ},
-        RegKey,
-    };
+    use winreg::enums::KEY_READ;
 
-    let io_to_locate_err = |io_err| Error::locate(LocateError::winreg(io_err));
+    // A missing key or value means that Steam was never installed (or was uninstalled)
+    let io_to_locate_err = |io_err: std::io::Error| {
+        if io_err.kind() == std::io::ErrorKind::NotFound {
+            Error::locate(LocateError::NotFound)
+        } else {
+            Error::locate(LocateError::winreg(io_err))
+        }
+    };
 
     // Locating the Steam installation location is a bit more complicated on Windows
-
-    // Steam's installation location can be found in the registry
-    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-    let installation_regkey = hklm
+    let installation_regkey = hive
         // 32-bit
         .open_subkey_with_flags("SOFTWARE\\Wow6432Node\\Valve\\Steam", KEY_READ)
         .or_else(|_| {
             // 64-bit
-            hklm.open_subkey_with_flags("SOFTWARE\\Valve\\Steam", KEY_READ)
+            hive.open_subkey_with_flags("SOFTWARE\\Valve\\Steam", KEY_READ)
         })
         .map_err(io_to_locate_err)?;
 
@@ -42,26 +134,152 @@ fn locate_steam_dir_helper() -> Result<PathBuf> {
         .map_err(io_to_locate_err)?;
 
     let install_path = PathBuf::from(install_path_str);
+    trace::debug!(
+        "located Steam from the registry: {}",
+        install_path.display()
+    );
     Ok(install_path)
 }
 
+#[cfg(target_os = "macos")]
+fn locate_steam_dirs_helper() -> Result<Vec<(PathBuf, InstallationType)>> {
+    let path = macos_steam_path()?;
+    let dirs = path
+        .is_dir()
+        .then_some((path, InstallationType::MacOs))
+        .into_iter()
+        .collect();
+    Ok(dirs)
+}
+
 #[cfg(target_os = "macos")]
 fn locate_steam_dir_helper() -> Result<PathBuf> {
     use crate::{error::LocateError, Error};
+
+    let install_path = macos_steam_path()?;
+    trace::debug!("trying candidate path {}", install_path.display());
+    if install_path.is_dir() {
+        Ok(install_path)
+    } else {
+        Err(Error::locate(LocateError::NotFound))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn macos_steam_path() -> Result<PathBuf> {
+    use crate::{error::LocateError, Error};
     // Steam's installation location is pretty easy to find on macOS, as it's always in
     // $USER/Library/Application Support
     let home_dir = home::home_dir().ok_or_else(|| Error::locate(LocateError::no_home()))?;
 
     // Find Library/Application Support/Steam
-    let install_path = home_dir.join("Library/Application Support/Steam");
-    Ok(install_path)
+    Ok(home_dir.join("Library/Application Support/Steam"))
 }
 
 #[cfg(target_os = "linux")]
 fn locate_steam_dir_helper() -> Result<PathBuf> {
-    use std::env;
+    use crate::error::{Error, LocateError};
+
+    let paths = linux_steam_paths();
+    let found = paths.iter().flatten().find(|(path, _)| {
+        trace::trace!("trying candidate path {}", path.display());
+        path.is_dir()
+    });
+    if let Some((path, _)) = found {
+        trace::debug!("located Steam at {}", path.display());
+        return Ok(path.to_owned());
+    }
 
-    use crate::error::{Error, LocateError, ValidationError};
+    trace::debug!("no candidate paths exist, checking running processes");
+    match steam_dir_from_processes(std::path::Path::new("/proc")) {
+        Some(path) => Ok(path),
+        None => {
+            paths?;
+            Err(Error::locate(LocateError::NotFound))
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn locate_steam_dirs_helper() -> Result<Vec<(PathBuf, InstallationType)>> {
+    let paths = linux_steam_paths();
+    let dirs: Vec<_> = paths
+        .iter()
+        .flatten()
+        .filter(|(path, _)| {
+            trace::trace!("trying candidate path {}", path.display());
+            path.is_dir()
+        })
+        .cloned()
+        .collect();
+    if !dirs.is_empty() {
+        return Ok(dirs);
+    }
+
+    trace::debug!("no candidate paths exist, checking running processes");
+    match steam_dir_from_processes(std::path::Path::new("/proc")) {
+        Some(path) => Ok(vec![(path, InstallationType::LinuxRunningProcess)]),
+        None => paths.map(|_| Vec::new()),
+    }
+}
+
+/// Derives the Steam installation from a running Steam process
+///
+/// This is a fallback for when none of the usual locations exist (e.g. an unusual `$HOME` or
+/// running in a container). Only each process's tiny `comm` file is read until a Steam process
+/// is found, so this stays cheap when Steam isn't running. The install dir is derived from
+///
+/// - The client binary (`<STEAM_DIR>/ubuntu12_32/steam`) through `/proc/<PID>/exe`
+/// - The launcher script (`<STEAM_DIR>/steam.sh`) through `/proc/<PID>/cmdline`
+#[cfg(target_os = "linux")]
+fn steam_dir_from_processes(proc_dir: &std::path::Path) -> Option<PathBuf> {
+    use std::{fs, path::Path};
+
+    let from_exe = |exe: PathBuf| {
+        let bin_dir = exe.parent()?;
+        let bin_name = bin_dir.file_name()?.to_str()?;
+        let steam_dir = bin_dir.parent()?;
+        matches!(bin_name, "ubuntu12_32" | "ubuntu12_64").then(|| steam_dir.to_owned())
+    };
+    let from_cmdline = |cmdline: Vec<u8>| {
+        cmdline
+            .split(|&b| b == 0)
+            .filter_map(|arg| std::str::from_utf8(arg).ok())
+            .find_map(|arg| Path::new(arg).ends_with("steam.sh").then(|| Path::new(arg)))
+            .and_then(Path::parent)
+            .map(Path::to_owned)
+    };
+
+    fs::read_dir(proc_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_str().is_some_and(is_pid))
+        .find_map(|entry| {
+            let process = entry.path();
+            let comm = fs::read_to_string(process.join("comm")).ok()?;
+            let steam_dir = match comm.trim_end() {
+                "steam" => from_exe(fs::read_link(process.join("exe")).ok()?),
+                "steam.sh" => from_cmdline(fs::read(process.join("cmdline")).ok()?),
+                _ => None,
+            }?;
+            trace::debug!(
+                "found a Steam process at {} pointing to {}",
+                process.display(),
+                steam_dir.display(),
+            );
+            steam_dir.is_dir().then_some(steam_dir)
+        })
+}
+
+#[cfg(target_os = "linux")]
+fn is_pid(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Every place that Steam could be installed on Linux in order of preference
+#[cfg(target_os = "linux")]
+fn linux_steam_paths() -> Result<Vec<(PathBuf, InstallationType)>> {
+    use crate::error::{Error, LocateError};
 
     // Steam's installation location is pretty easy to find on Linux, too, thanks to the symlink in $USER
     let home_dir = home::home_dir().ok_or_else(|| Error::locate(LocateError::no_home()))?;
@@ -70,24 +288,275 @@ fn locate_steam_dir_helper() -> Result<PathBuf> {
         Err(_) => home_dir.join("snap"),
     };
 
-    let steam_paths = vec![
-        // Flatpak steam install directories
-        home_dir.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"),
-        home_dir.join(".var/app/com.valvesoftware.Steam/.steam/steam"),
-        home_dir.join(".var/app/com.valvesoftware.Steam/.steam/root"),
+    // The Flatpak's data dir sticks around after it's uninstalled, so only prefer it when the
+    // Flatpak is actually installed
+    let flatpak_installed = flatpak_installations(&home_dir, |var| env::var_os(var))
+        .iter()
+        .any(|root| root.join("app").join(FLATPAK_APP_ID).is_dir());
+
+    Ok(ordered_linux_steam_paths(
+        &home_dir,
+        &snap_dir,
+        flatpak_installed,
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn ordered_linux_steam_paths(
+    home_dir: &std::path::Path,
+    snap_dir: &std::path::Path,
+    flatpak_installed: bool,
+) -> Vec<(PathBuf, InstallationType)> {
+    let flatpak_paths = flatpak_steam_paths(home_dir)
+        .into_iter()
+        .map(|path| (path, InstallationType::LinuxFlatpak));
+    let standard_paths = [
         // Standard install directories
-        home_dir.join(".local/share/Steam"),
-        home_dir.join(".steam/steam"),
-        home_dir.join(".steam/root"),
-        home_dir.join(".steam"),
+        (
+            home_dir.join(".local/share/Steam"),
+            InstallationType::LinuxNative,
+        ),
+        (home_dir.join(".steam/steam"), InstallationType::LinuxNative),
+        (home_dir.join(".steam/root"), InstallationType::LinuxNative),
+        (home_dir.join(".steam"), InstallationType::LinuxNative),
         // Snap steam install directories
-        snap_dir.join("steam/common/.local/share/Steam"),
-        snap_dir.join("steam/common/.steam/steam"),
-        snap_dir.join("steam/common/.steam/root"),
+        (
+            snap_dir.join("steam/common/.local/share/Steam"),
+            InstallationType::LinuxSnap,
+        ),
+        (
+            snap_dir.join("steam/common/.steam/steam"),
+            InstallationType::LinuxSnap,
+        ),
+        (
+            snap_dir.join("steam/common/.steam/root"),
+            InstallationType::LinuxSnap,
+        ),
     ];
 
-    steam_paths
-        .into_iter()
-        .find(|x| x.is_dir())
-        .ok_or_else(|| Error::validation(ValidationError::missing_dir()))
+    if flatpak_installed {
+        flatpak_paths.chain(standard_paths).collect()
+    } else {
+        standard_paths.into_iter().chain(flatpak_paths).collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+const FLATPAK_APP_ID: &str = "com.valvesoftware.Steam";
+
+/// The Steam install directories within the Steam Flatpak's data dir
+///
+/// Flatpak always keeps an app's data under `~/.var/app/<APP_ID>` regardless of which
+/// installation or branch (e.g. beta) the app was installed from
+#[cfg(target_os = "linux")]
+fn flatpak_steam_paths(home_dir: &std::path::Path) -> [PathBuf; 3] {
+    let data_dir = home_dir.join(".var/app").join(FLATPAK_APP_ID);
+    [
+        data_dir.join(".local/share/Steam"),
+        data_dir.join(".steam/steam"),
+        data_dir.join(".steam/root"),
+    ]
+}
+
+/// The Flatpak installations that Steam could be installed in
+///
+/// This is the per-user installation (`$FLATPAK_USER_DIR` or `~/.local/share/flatpak`) followed
+/// by the system-wide installation (`$FLATPAK_SYSTEM_DIR` or `/var/lib/flatpak`)
+#[cfg(target_os = "linux")]
+fn flatpak_installations(
+    home_dir: &std::path::Path,
+    get_var: impl Fn(&str) -> Option<OsString>,
+) -> [PathBuf; 2] {
+    let user_dir = get_var("FLATPAK_USER_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir.join(".local/share/flatpak"));
+    let system_dir = get_var("FLATPAK_SYSTEM_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/var/lib/flatpak"));
+    [user_dir, system_dir]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::__private_tests::{prelude::expect_test_env, TestResult};
+
+    #[test]
+    fn env_var_precedence() -> TestResult {
+        let temp_steam_dir = expect_test_env();
+        let steam_path = temp_steam_dir.steam_dir().path().to_owned();
+        let missing_path = steam_path.join("missing");
+
+        // Only `STEAM_ROOT` is set
+        let located = steam_dir_from_env(|var| {
+            (var == "STEAM_ROOT").then(|| steam_path.clone().into_os_string())
+        });
+        assert_eq!(located.as_ref(), Some(&steam_path));
+
+        // `STEAM_BASE_FOLDER` takes precedence, but only when it points to a valid dir
+        let located = steam_dir_from_env(|var| match var {
+            "STEAM_BASE_FOLDER" => Some(missing_path.clone().into_os_string()),
+            "STEAM_ROOT" => Some(steam_path.clone().into_os_string()),
+            _ => None,
+        });
+        assert_eq!(located.as_ref(), Some(&steam_path));
+
+        // Nothing set
+        assert_eq!(steam_dir_from_env(|_| None), None);
+
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn flatpak_installations_from_env() {
+        let home_dir = PathBuf::from("/home/user");
+        let installations = flatpak_installations(&home_dir, |_| None);
+        assert_eq!(
+            installations,
+            [
+                home_dir.join(".local/share/flatpak"),
+                PathBuf::from("/var/lib/flatpak"),
+            ]
+        );
+
+        let installations = flatpak_installations(&home_dir, |var| match var {
+            "FLATPAK_USER_DIR" => Some("/custom/user".into()),
+            "FLATPAK_SYSTEM_DIR" => Some("/custom/system".into()),
+            _ => None,
+        });
+        assert_eq!(
+            installations,
+            [
+                PathBuf::from("/custom/user"),
+                PathBuf::from("/custom/system")
+            ]
+        );
+
+        let flatpak_paths = flatpak_steam_paths(&home_dir);
+        assert!(flatpak_paths
+            .iter()
+            .all(|path| path.starts_with("/home/user/.var/app/com.valvesoftware.Steam")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_installation_types() {
+        let home_dir = PathBuf::from("/home/user");
+        let snap_dir = home_dir.join("snap");
+        let type_of = |paths: &[(PathBuf, InstallationType)], suffix: &str| {
+            paths
+                .iter()
+                .find(|(path, _)| path.ends_with(suffix))
+                .map(|(_, ty)| *ty)
+        };
+
+        let paths = ordered_linux_steam_paths(&home_dir, &snap_dir, false);
+        assert_eq!(paths[0].1, InstallationType::LinuxNative);
+        assert_eq!(paths.last().unwrap().1, InstallationType::LinuxFlatpak);
+        assert_eq!(
+            type_of(&paths, "steam/common/.local/share/Steam"),
+            Some(InstallationType::LinuxSnap)
+        );
+
+        // An installed Flatpak takes precedence
+        let paths = ordered_linux_steam_paths(&home_dir, &snap_dir, true);
+        assert_eq!(paths[0].1, InstallationType::LinuxFlatpak);
+        assert!(paths[0].0.starts_with("/home/user/.var/app"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn running_process() -> TestResult {
+        use std::{fs, os::unix::fs::symlink};
+
+        use crate::__private_tests::temp::TempDir;
+
+        let tmp = TempDir::new()?;
+        let steam_dir = tmp.path().join("Steam");
+        fs::create_dir_all(steam_dir.join("ubuntu12_32"))?;
+        let proc_dir = tmp.path().join("proc");
+        let add_process = |pid: &str, comm: &str| -> std::io::Result<PathBuf> {
+            let process = proc_dir.join(pid);
+            fs::create_dir_all(&process)?;
+            fs::write(process.join("comm"), format!("{comm}\n"))?;
+            Ok(process)
+        };
+
+        // Nothing that looks like Steam
+        add_process("1", "systemd")?;
+        fs::create_dir_all(proc_dir.join("self"))?;
+        fs::write(proc_dir.join("self").join("comm"), "steam\n")?;
+        assert_eq!(steam_dir_from_processes(&proc_dir), None);
+
+        // The launcher script
+        let launcher = add_process("20", "steam.sh")?;
+        let script = steam_dir.join("steam.sh");
+        fs::write(
+            launcher.join("cmdline"),
+            format!("/bin/bash\0{}\0-silent\0", script.display()),
+        )?;
+        assert_eq!(steam_dir_from_processes(&proc_dir), Some(steam_dir.clone()));
+        fs::remove_dir_all(launcher)?;
+
+        // The client binary
+        let client = add_process("30", "steam")?;
+        symlink(
+            steam_dir.join("ubuntu12_32").join("steam"),
+            client.join("exe"),
+        )?;
+        assert_eq!(steam_dir_from_processes(&proc_dir), Some(steam_dir));
+
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    mod windows {
+        use super::*;
+
+        use winreg::{enums::HKEY_CURRENT_USER, RegKey};
+
+        // A scratch key under the current user (which is writable without elevated permissions) acts
+        // as a stand-in for `HKEY_LOCAL_MACHINE`
+        struct TempHive {
+            path: String,
+            key: RegKey,
+        }
+
+        impl TempHive {
+            fn new() -> std::io::Result<Self> {
+                let path = format!("Software\\steamlocate-test-{}", std::process::id());
+                let (key, _) = RegKey::predef(HKEY_CURRENT_USER).create_subkey(&path)?;
+                Ok(Self { path, key })
+            }
+        }
+
+        impl Drop for TempHive {
+            fn drop(&mut self) {
+                let _ = RegKey::predef(HKEY_CURRENT_USER).delete_subkey_all(&self.path);
+            }
+        }
+
+        #[test]
+        fn custom_hive() -> crate::__private_tests::TestResult {
+            let hive = TempHive::new()?;
+            let err = locate_steam_dir_from_hive(&hive.key).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    crate::Error::FailedLocate(crate::error::LocateError::NotFound)
+                ),
+                "{err:?}",
+            );
+
+            let (steam_key, _) = hive.key.create_subkey("SOFTWARE\\Valve\\Steam")?;
+            let install_path = String::from("C:\\Some\\Custom\\Steam");
+            steam_key.set_value("InstallPath", &install_path)?;
+            let located = locate_steam_dir_from_hive(&hive.key)?;
+            assert_eq!(located, PathBuf::from(&install_path));
+
+            Ok(())
+        }
+    }
 }
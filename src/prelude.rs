@@ -0,0 +1,39 @@
+//! Convenient re-exports of the most commonly used types
+//!
+//! Everything here is also available from the crate root or its respective module. This just saves
+//! on imports when you're working with a lot of the API at once
+//!
+//! The iterators are all named `Iter` within their own modules, so they're re-exported here as
+//! [`AppIter`], [`LibraryIter`], and [`ShortcutIter`] to avoid clashing
+//!
+//! # Example
+//!
+//! ```
+//! use steamlocate::prelude::*;
+//!
+//! # use steamlocate::__private_tests::prelude::{expect_test_env, TestError};
+//! # let temp_steam_dir = expect_test_env();
+//! # let steam_dir = temp_steam_dir.steam_dir();
+//! # /*
+//! let steam_dir = SteamDir::locate()?;
+//! # */
+//! let libraries: Vec<Library> = steam_dir.libraries()?.collect::<Result<_>>()?;
+//! for library in &libraries {
+//!     for app in library.apps() {
+//!         let app: App = app?;
+//!         if app.universe == Some(Universe::Public) {
+//!             println!("{}", app.app_id);
+//!         }
+//!     }
+//! }
+//! # Ok::<_, TestError>(())
+//! ```
+
+pub use crate::{
+    app::{Iter as AppIter, StateFlag, StateFlags, Universe},
+    error::{Error, Result},
+    library::{Iter as LibraryIter, LibraryDiff},
+    shortcut::Iter as ShortcutIter,
+    App, AutoUpdateWindow, BandwidthSettings, CompatTool, Entry, Library, ScanResult, Shortcut,
+    SteamDir,
+};
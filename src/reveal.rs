@@ -0,0 +1,56 @@
+//! Helpers for showing paths in the system's file manager
+//!
+//! Everything here only builds a [`Command`]. Nothing gets spawned, so it's up to you to decide
+//! when (and if) to run it
+
+use std::{path::Path, process::Command};
+
+/// Builds the platform-appropriate command to reveal `path` in a file manager
+///
+/// This uses `explorer` on Windows, `open` on macOS, and `xdg-open` everywhere else
+///
+/// # Example
+///
+/// ```
+/// # use steamlocate::__private_tests::prelude::*;
+/// # let temp_steam_dir = expect_test_env();
+/// # let steam_dir = temp_steam_dir.steam_dir();
+/// # /*
+/// let steam_dir = SteamDir::locate()?;
+/// # */
+/// let mut command = steamlocate::reveal::reveal_command(steam_dir.path());
+/// # /*
+/// command.spawn()?;
+/// # */
+/// # let _ = &mut command;
+/// # Ok::<_, TestError>(())
+/// ```
+pub fn reveal_command(path: &Path) -> Command {
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    let mut command = Command::new(program);
+    command.arg(path);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reveal_command_passes_path() {
+        let path = Path::new("some").join("dir");
+        let command = reveal_command(&path);
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, [path.as_os_str()]);
+
+        #[cfg(target_os = "linux")]
+        assert_eq!(command.get_program(), "xdg-open");
+    }
+}
@@ -0,0 +1,28 @@
+//! Batch scanning that collects errors instead of stopping at the first one
+//!
+//! _See [`SteamDir::scan_apps()`][crate::SteamDir::scan_apps] for running a scan_
+
+use crate::{App, Error, Library};
+
+/// Everything found while scanning all of an installation's apps
+///
+/// Returned from calling [`SteamDir::scan_apps()`][crate::SteamDir::scan_apps]
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct ScanResult {
+    /// Every app that was parsed successfully along with the library that contains it
+    pub apps: Vec<(App, Library)>,
+    /// The apps that failed to load, keyed by their app ID
+    ///
+    /// This includes apps that are listed in `libraryfolders.vdf` but are missing their manifest
+    pub errors: Vec<(u32, Error)>,
+    /// Libraries that couldn't be read at all, so none of their apps were scanned
+    pub library_errors: Vec<Error>,
+}
+
+impl ScanResult {
+    /// Whether the scan finished without running into any errors
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty() && self.library_errors.is_empty()
+    }
+}
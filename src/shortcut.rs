@@ -1,13 +1,12 @@
-// HACK: This is all hacky and should be replaced with proper binary VDF parsing
-
 use std::{
-    fs, io,
-    iter::Peekable,
+    env, fs, io,
     path::{Path, PathBuf},
+    process::Command,
     slice,
 };
 
 use crate::{
+    config,
     error::{ParseError, ParseErrorKind},
     Error, Result,
 };
@@ -31,6 +30,8 @@ pub struct Shortcut {
     pub executable: String,
     /// The directory that the application should be run in
     pub start_dir: String,
+    tags: Vec<String>,
+    launch_options: String,
 }
 
 impl Shortcut {
@@ -41,31 +42,300 @@ impl Shortcut {
             app_name,
             executable,
             start_dir,
+            tags: Vec::new(),
+            launch_options: String::new(),
         }
     }
 
+    /// The tags (aka collections) that the shortcut has been added to in Steam
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for shortcut in steam_dir.shortcuts()? {
+    ///     let shortcut = shortcut?;
+    ///     println!("{} is tagged with {:?}", shortcut.app_name, shortcut.tags());
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// The extra arguments that the user set for the shortcut in Steam
+    ///
+    /// This is empty when no launch options were set
+    pub fn launch_options(&self) -> &str {
+        &self.launch_options
+    }
+
     /// The shortcut's Steam ID calculated from the executable path and app name
     pub fn steam_id(&self) -> u64 {
-        let executable = self.executable.as_bytes();
-        let app_name = self.app_name.as_bytes();
+        calculate_steam_id(&self.executable, &self.app_name)
+    }
 
-        let algorithm = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+    /// The 64-bit ID that Big Picture (and the legacy grid artwork) uses for the shortcut
+    ///
+    /// This is derived purely from the executable and app name. The high 32 bits are the CRC-32
+    /// of the executable followed by the app name with the top bit set, and the low 32 bits are
+    /// always `0x02000000`. It's the same value as [`Shortcut::steam_id()`]
+    pub fn big_picture_id(&self) -> u64 {
+        self.steam_id()
+    }
+
+    /// The game ID used to launch the shortcut via `steam://rungameid/<GAME_ID>`
+    ///
+    /// The high 32 bits are the shortcut's [`app_id`][Self::app_id] and the low 32 bits are
+    /// always `0x02000000`. This can differ from [`Shortcut::big_picture_id()`] since newer
+    /// versions of Steam don't derive the `app_id` from the shortcut's executable and name
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let moonlighter = steam_dir.shortcuts()?.next().unwrap()?;
+    /// let launch_url = format!("steam://rungameid/{}", moonlighter.rungameid());
+    /// # assert_eq!(launch_url, "steam://rungameid/17731278641934893056");
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn rungameid(&self) -> u64 {
+        ((self.app_id as u64) << 32) | 0x02000000
+    }
+
+    /// The path to the shortcut's executable with any surrounding quotes removed
+    ///
+    /// Relative executables are resolved against the shortcut's (also unquoted)
+    /// [`start_dir`][Self::start_dir]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let moonlighter = steam_dir.shortcuts()?.next().unwrap()?;
+    /// assert_eq!(
+    ///     moonlighter.resolved_executable(),
+    ///     Path::new("/home/cosmic/GOG Games/Moonlighter/start.sh"),
+    /// );
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn resolved_executable(&self) -> PathBuf {
+        let executable = Path::new(unquote(&self.executable));
+        if executable.is_absolute() {
+            executable.to_owned()
+        } else {
+            Path::new(unquote(&self.start_dir)).join(executable)
+        }
+    }
+
+    /// Whether the shortcut's executable still exists
+    ///
+    /// The executable is resolved with [`Shortcut::resolved_executable()`]. Directories count as
+    /// existing, so that macOS `.app` bundles are handled. When a bare program name (e.g. `anki`)
+    /// doesn't exist relative to the start dir then the directories in `$PATH` are searched
+    /// instead
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for shortcut in steam_dir.shortcuts()? {
+    ///     let shortcut = shortcut?;
+    ///     if !shortcut.executable_exists() {
+    ///         println!("{} points to a missing executable", shortcut.app_name);
+    ///     }
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn executable_exists(&self) -> bool {
+        if self.resolved_executable().exists() {
+            return true;
+        }
+
+        let executable = Path::new(unquote(&self.executable));
+        let is_bare_name = executable.components().count() == 1 && !executable.is_absolute();
+        is_bare_name
+            && env::var_os("PATH").is_some_and(|paths| {
+                env::split_paths(&paths).any(|dir| dir.join(executable).exists())
+            })
+    }
+
+    /// Builds a [`Command`] that launches the shortcut like Steam would
+    ///
+    /// The (unquoted) executable is run from the (unquoted) [`start_dir`][Self::start_dir] with
+    /// the shortcut's [`launch_options()`][Self::launch_options] as its arguments. Launch options
+    /// get split like a shell would and can use `%command%` to wrap the executable with another
+    /// program, where any leading `NAME=value` pairs are set as environment variables instead
+    /// (e.g. `MANGOHUD=1 gamemoderun %command% --fullscreen`). Bare program names (e.g. `anki`)
+    /// that don't exist in the start dir are left for the OS to look up through `$PATH`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// let moonlighter = steam_dir.shortcuts()?.next().unwrap()?;
+    /// let command = moonlighter.launch_command();
+    /// # /*
+    /// command.spawn()?;
+    /// # */
+    /// assert_eq!(
+    ///     command.get_program(),
+    ///     Path::new("/home/cosmic/GOG Games/Moonlighter/start.sh"),
+    /// );
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn launch_command(&self) -> Command {
+        let executable = Path::new(unquote(&self.executable));
+        let resolved = self.resolved_executable();
+        let is_bare_name = executable.components().count() == 1 && !executable.is_absolute();
+        let program = if is_bare_name && !resolved.exists() {
+            executable.to_owned()
+        } else {
+            resolved
+        };
+
+        let args = split_launch_options(&self.launch_options);
+        let (before, after) = match args.iter().position(|arg| arg == "%command%") {
+            Some(pos) => (&args[..pos], &args[pos + 1..]),
+            None => (&[][..], &args[..]),
+        };
+        let num_env_vars = before
+            .iter()
+            .take_while(|arg| is_env_assignment(arg))
+            .count();
+        let (env_vars, wrapper) = before.split_at(num_env_vars);
+
+        let mut command = match wrapper.split_first() {
+            Some((wrapper, wrapper_args)) => {
+                let mut command = Command::new(wrapper);
+                command.args(wrapper_args).arg(program);
+                command
+            }
+            None => Command::new(program),
+        };
+        command.args(after);
+        for env_var in env_vars {
+            if let Some((name, value)) = env_var.split_once('=') {
+                command.env(name, value);
+            }
+        }
+        let start_dir = unquote(&self.start_dir);
+        if !start_dir.is_empty() {
+            command.current_dir(start_dir);
+        }
 
-        let mut digest = algorithm.digest();
-        digest.update(executable);
-        digest.update(app_name);
+        command
+    }
+}
 
-        let top = digest.finalize() | 0x80000000;
-        ((top as u64) << 32) | 0x02000000
+/// Splits launch options into their arguments similar to how a shell would
+///
+/// Arguments are separated by whitespace and can be grouped with either single or double quotes
+fn split_launch_options(launch_options: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    // Tracks quoted empty strings (`""`) which are still an argument
+    let mut in_arg = false;
+    let mut quote = None;
+    for c in launch_options.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
     }
+
+    args
+}
+
+/// Whether `arg` is a shell style environment variable assignment e.g. `PROTON_LOG=1`
+fn is_env_assignment(arg: &str) -> bool {
+    arg.split_once('=').is_some_and(|(name, _)| {
+        name.chars().next().is_some_and(|c| !c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Strips a single pair of surrounding double quotes, which Steam adds to paths by default
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(s)
+}
+
+/// Calculates a shortcut's Steam ID from its executable and app name
+///
+/// The high 32 bits are the CRC-32 (ISO-HDLC aka the one used by zlib) of the executable followed
+/// by the app name with the top bit set. The low 32 bits are always `0x02000000`
+pub(crate) fn calculate_steam_id(executable: &str, app_name: &str) -> u64 {
+    let algorithm = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+
+    let mut digest = algorithm.digest();
+    digest.update(executable.as_bytes());
+    digest.update(app_name.as_bytes());
+
+    let top = digest.finalize() | 0x80000000;
+    ((top as u64) << 32) | 0x02000000
 }
 
 /// An [`Iterator`] over a Steam installation's [`Shortcut`]s
 ///
-/// Returned from calling [`SteamDir::shortcuts()`][super::SteamDir::shortcuts]
+/// Returned from calling [`SteamDir::shortcuts()`][super::SteamDir::shortcuts] or
+/// [`SteamDir::shortcuts_for_user()`][super::SteamDir::shortcuts_for_user]
+///
+/// Each user's `shortcuts.vdf` is handled independently. An empty file yields no shortcuts while
+/// a malformed (e.g. truncated) one yields a single error, and iteration then continues with the
+/// next user's shortcuts
+///
+/// The `userdata` directory is listed up front, so the iterator doesn't keep any directory handles
+/// open between calls to `next()`. Only one `shortcuts.vdf` is open at a time while it's read
 pub struct Iter {
     dir: PathBuf,
-    read_dir: fs::ReadDir,
+    /// The `userdata/<USER_ID>` directories that are left to read shortcuts from
+    user_dirs: std::vec::IntoIter<io::Result<PathBuf>>,
     pending: std::vec::IntoIter<Shortcut>,
 }
 
@@ -80,13 +350,65 @@ impl Iter {
             ));
         }
 
-        let read_dir = fs::read_dir(&user_data).map_err(|io| Error::io(io, &user_data))?;
+        // Collected eagerly so that the handle is closed right away instead of being held for the
+        // whole traversal, which adds up when many iterators are alive at once
+        let user_dirs: Vec<_> = fs::read_dir(&user_data)
+            .map_err(|io| Error::io(io, &user_data))?
+            .filter_map(|maybe_entry| match maybe_entry {
+                // Skip the anonymous `0` user, `ac`, and anything else that isn't an account
+                Ok(entry) if config::user_data_account_id(&entry.file_name()).is_none() => None,
+                maybe_entry => Some(maybe_entry.map(|entry| entry.path())),
+            })
+            .collect();
         Ok(Self {
             dir: user_data,
-            read_dir,
+            user_dirs: user_dirs.into_iter(),
+            pending: Vec::new().into_iter(),
+        })
+    }
+
+    pub(crate) fn for_user(steam_dir: &Path, user_id: u32) -> Result<Self> {
+        let user_dir = steam_dir.join("userdata").join(user_id.to_string());
+        if !user_dir.is_dir() {
+            return Err(Error::parse(
+                ParseErrorKind::Shortcut,
+                ParseError::missing(),
+                &user_dir,
+            ));
+        }
+
+        Ok(Self {
+            dir: user_dir.clone(),
+            user_dirs: vec![Ok(user_dir)].into_iter(),
             pending: Vec::new().into_iter(),
         })
     }
+
+    /// Filters the shortcuts down to ones that have the `tag`
+    ///
+    /// Errors are still passed through
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use steamlocate::__private_tests::prelude::*;
+    /// # let temp_steam_dir: TempSteamDir = SampleShortcuts::JustGogMoonlighter.try_into()?;
+    /// # let steam_dir = temp_steam_dir.steam_dir();
+    /// # /*
+    /// let steam_dir = SteamDir::locate()?;
+    /// # */
+    /// for shortcut in steam_dir.shortcuts()?.with_tag("Favorites") {
+    ///     println!("{} is a favorite", shortcut?.app_name);
+    /// }
+    /// # Ok::<_, TestError>(())
+    /// ```
+    pub fn with_tag(self, tag: &str) -> impl Iterator<Item = Result<Shortcut>> {
+        let tag = tag.to_owned();
+        self.filter(move |maybe_shortcut| match maybe_shortcut {
+            Ok(shortcut) => shortcut.tags.contains(&tag),
+            Err(_) => true,
+        })
+    }
 }
 
 impl Iterator for Iter {
@@ -99,10 +421,10 @@ impl Iterator for Iter {
             }
 
             // Need to parse the next set of pending shortcuts
-            let maybe_entry = self.read_dir.next()?;
-            match maybe_entry {
-                Ok(entry) => {
-                    let shortcuts_path = entry.path().join("config").join("shortcuts.vdf");
+            let maybe_user_dir = self.user_dirs.next()?;
+            match maybe_user_dir {
+                Ok(user_dir) => {
+                    let shortcuts_path = user_dir.join("config").join("shortcuts.vdf");
                     match fs::read(&shortcuts_path) {
                         Ok(contents) => {
                             if let Some(shortcuts) = parse_shortcuts(&contents) {
@@ -134,46 +456,40 @@ impl Iterator for Iter {
     }
 }
 
-/// Advances `it` until right after the matching `needle`
+/// A value from a binary VDF file
 ///
-/// Only works if the starting byte is not used anywhere else in the needle. This works well when
-/// finding keys since the starting byte indicates the type and wouldn't be used in the key
-#[must_use]
-fn after_many_case_insensitive(it: &mut Peekable<slice::Iter<u8>>, needle: &[u8]) -> bool {
-    loop {
-        let mut needle_it = needle.iter();
-        let b = match it.next() {
-            Some(b) => b,
-            None => return false,
-        };
+/// Only the types that shortcuts care about are kept around. Everything else is skipped over
+enum Value {
+    Str(String),
+    Int(u32),
+    Object(Vec<(String, Value)>),
+    Other,
+}
 
-        let maybe_needle_b = needle_it.next();
-        if maybe_u8_eq_ignore_ascii_case(maybe_needle_b, Some(b)) {
-            loop {
-                if needle_it.len() == 0 {
-                    return true;
-                }
+impl Value {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(s) => Some(s),
+            _ => None,
+        }
+    }
 
-                let maybe_b = it.peek();
-                let maybe_needle_b = needle_it.next();
-                if maybe_u8_eq_ignore_ascii_case(maybe_needle_b, maybe_b.copied()) {
-                    let _ = it.next();
-                } else {
-                    break;
-                }
-            }
+    fn as_u32(&self) -> Option<u32> {
+        match self {
+            Self::Int(i) => Some(*i),
+            _ => None,
         }
     }
-}
 
-fn maybe_u8_eq_ignore_ascii_case(maybe_b1: Option<&u8>, maybe_b2: Option<&u8>) -> bool {
-    maybe_b1
-        .zip(maybe_b2)
-        .map(|(b1, b2)| b1.eq_ignore_ascii_case(b2))
-        .unwrap_or_default()
+    fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Self::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
 }
 
-fn parse_value_str(it: &mut Peekable<slice::Iter<u8>>) -> Option<String> {
+fn parse_value_str(it: &mut slice::Iter<u8>) -> Option<String> {
     let mut buff = Vec::new();
     loop {
         let b = it.next()?;
@@ -185,39 +501,98 @@ fn parse_value_str(it: &mut Peekable<slice::Iter<u8>>) -> Option<String> {
     }
 }
 
-fn parse_value_u32(it: &mut Peekable<slice::Iter<u8>>) -> Option<u32> {
+fn parse_value_u32(it: &mut slice::Iter<u8>) -> Option<u32> {
     let bytes = [*it.next()?, *it.next()?, *it.next()?, *it.next()?];
     Some(u32::from_le_bytes(bytes))
 }
 
-fn parse_shortcuts(contents: &[u8]) -> Option<Vec<Shortcut>> {
-    let mut it = contents.iter().peekable();
-    let mut shortcuts = Vec::new();
+fn skip_bytes(it: &mut slice::Iter<u8>, len: usize) -> Option<()> {
+    if it.len() < len {
+        return None;
+    }
+    it.nth(len - 1).map(|_| ())
+}
 
+/// Parses the entries of an object up to and including its end byte
+///
+/// Each entry is a type byte followed by the nul-terminated key and then the value
+fn parse_object(it: &mut slice::Iter<u8>) -> Option<Vec<(String, Value)>> {
+    let mut entries = Vec::new();
     loop {
-        if !after_many_case_insensitive(&mut it, b"\x02appid\x00") {
-            return Some(shortcuts);
+        let ty = *it.next()?;
+        if ty == 0x08 {
+            break Some(entries);
         }
-        let app_id = parse_value_u32(&mut it)?;
 
-        if !after_many_case_insensitive(&mut it, b"\x01AppName\x00") {
-            return None;
-        }
-        let app_name = parse_value_str(&mut it)?;
+        let key = parse_value_str(it)?;
+        let value = match ty {
+            0x00 => Value::Object(parse_object(it)?),
+            0x01 => Value::Str(parse_value_str(it)?),
+            0x02 => Value::Int(parse_value_u32(it)?),
+            // float, pointer, and color
+            0x03 | 0x04 | 0x06 => {
+                skip_bytes(it, 4)?;
+                Value::Other
+            }
+            // uint64 and int64
+            0x07 | 0x0a => {
+                skip_bytes(it, 8)?;
+                Value::Other
+            }
+            _ => return None,
+        };
+        entries.push((key, value));
+    }
+}
 
-        if !after_many_case_insensitive(&mut it, b"\x01Exe\x00") {
-            return None;
-        }
-        let executable = parse_value_str(&mut it)?;
+/// Builds a shortcut from the entries of its block
+///
+/// Keys are matched case-insensitively and can appear in any order
+fn parse_shortcut(entries: &[(String, Value)]) -> Option<Shortcut> {
+    let get = |key: &str| {
+        entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    };
 
-        if !after_many_case_insensitive(&mut it, b"\x01StartDir\x00") {
-            return None;
-        }
-        let start_dir = parse_value_str(&mut it)?;
+    let app_id = get("appid")?.as_u32()?;
+    let app_name = get("AppName")?.as_str()?.to_owned();
+    let executable = get("Exe")?.as_str()?.to_owned();
+    let start_dir = get("StartDir")?.as_str()?.to_owned();
+    let mut shortcut = Shortcut::new(app_id, app_name, executable, start_dir);
+
+    // Tags are stored as a list with the indices as the keys e.g. `\x01 0 \x00 <tag> \x00`
+    if let Some(tags) = get("tags") {
+        shortcut.tags = tags
+            .as_object()?
+            .iter()
+            .filter_map(|(_, tag)| tag.as_str().map(ToOwned::to_owned))
+            .collect();
+    }
+    if let Some(launch_options) = get("LaunchOptions") {
+        shortcut.launch_options = launch_options.as_str()?.to_owned();
+    }
+
+    Some(shortcut)
+}
+
+fn parse_shortcuts(contents: &[u8]) -> Option<Vec<Shortcut>> {
+    let mut it = contents.iter();
 
-        let shortcut = Shortcut::new(app_id, app_name, executable, start_dir);
-        shortcuts.push(shortcut);
+    // The root `shortcuts` object. An empty file just means there aren't any shortcuts
+    let Some(&ty) = it.next() else {
+        return Some(Vec::new());
+    };
+    if ty != 0x00 {
+        return None;
     }
+    let _root_key = parse_value_str(&mut it)?;
+
+    parse_object(&mut it)?
+        .iter()
+        .map(|(_index, block)| parse_shortcut(block.as_object()?))
+        .collect()
 }
 
 #[cfg(test)]
@@ -236,18 +611,24 @@ mod tests {
                     app_name: "Anki".into(),
                     executable: "\"anki\"".into(),
                     start_dir: "\"./\"".into(),
+                    tags: Vec::new(),
+                    launch_options: String::new(),
                 },
                 Shortcut {
                     app_id: 2492174738,
                     app_name: "LibreOffice Calc".into(),
                     executable: "\"libreoffice\"".into(),
                     start_dir: "\"./\"".into(),
+                    tags: Vec::new(),
+                    launch_options: "--calc".into(),
                 },
                 Shortcut {
                     app_id: 3703025501,
                     app_name: "foo.sh".into(),
                     executable: "\"/usr/local/bin/foo.sh\"".into(),
                     start_dir: "\"/usr/local/bin/\"".into(),
+                    tags: Vec::new(),
+                    launch_options: String::new(),
                 }
             ],
         );
@@ -259,6 +640,13 @@ mod tests {
             steam_ids,
             [0xe89614fe02000000, 0xdb01c79902000000, 0x9d55017302000000,]
         );
+        let big_picture_ids: Vec<_> = shortcuts.iter().map(Shortcut::big_picture_id).collect();
+        assert_eq!(big_picture_ids, steam_ids);
+        let rungameids: Vec<_> = shortcuts.iter().map(Shortcut::rungameid).collect();
+        assert_eq!(
+            rungameids,
+            [0xa6132c0502000000, 0x948b919202000000, 0xdcb7af5d02000000]
+        );
 
         let contents = include_bytes!("../tests/sample_data/shortcuts_different_key_case.vdf");
         let shortcuts = parse_shortcuts(contents).unwrap();
@@ -269,6 +657,155 @@ mod tests {
                 app_name: "Second Life".into(),
                 executable: "\"/Applications/Second Life Viewer.app\"".into(),
                 start_dir: "\"/Applications/\"".into(),
+                tags: Vec::new(),
+                launch_options: String::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn steam_id_vectors() {
+        // (executable, app name) pairs taken from real `shortcuts.vdf` files
+        let vectors = [
+            ("\"anki\"", "Anki", 0xe89614fe02000000),
+            ("\"libreoffice\"", "LibreOffice Calc", 0xdb01c79902000000),
+            ("\"/usr/local/bin/foo.sh\"", "foo.sh", 0x9d55017302000000),
+            (
+                "\"/Applications/Second Life Viewer.app\"",
+                "Second Life",
+                0xfdd972df02000000,
+            ),
+            (
+                "\"/home/cosmic/GOG Games/Moonlighter/start.sh\"",
+                "Moonlighter",
+                0xd8f2d3b802000000,
+            ),
+            // The top bit is always set, even when the CRC itself is zero
+            ("", "", 0x8000000002000000),
+        ];
+        for (executable, app_name, expected) in vectors {
+            assert_eq!(
+                calculate_steam_id(executable, app_name),
+                expected,
+                "{executable} {app_name}",
+            );
+        }
+    }
+
+    #[test]
+    fn tags() {
+        let contents =
+            b"\x00shortcuts\x00\x000\x00\x02appid\x00\x05,\x13\xa6\x01AppName\x00Anki\x00\
+            \x01Exe\x00\"anki\"\x00\x01StartDir\x00\"./\"\x00\x00tags\x00\x010\x00Favorites\x00\
+            \x011\x00Study\x00\x08\x08\x001\x00\x02appid\x00\x92\x91\x8b\x94\x01AppName\x00\
+            LibreOffice Calc\x00\x01Exe\x00\"libreoffice\"\x00\x01StartDir\x00\"./\"\x00\
+            \x00tags\x00\x08\x08\x08\x08";
+        let shortcuts = parse_shortcuts(contents).unwrap();
+        let tags: Vec<_> = shortcuts.iter().map(|shortcut| shortcut.tags()).collect();
+        assert_eq!(tags, [&["Favorites", "Study"][..], &[]]);
+    }
+
+    #[test]
+    fn executable_exists() {
+        let dir = std::env::temp_dir();
+        let dir_str = dir.to_str().unwrap();
+        let mut shortcut =
+            Shortcut::new(1, "Temp".into(), format!("\"{dir_str}\""), "\"./\"".into());
+        assert_eq!(shortcut.resolved_executable(), dir);
+        // Directories count, so `.app` bundles work
+        assert!(shortcut.executable_exists());
+
+        shortcut.executable = "\"does-not-exist.sh\"".into();
+        shortcut.start_dir = format!("\"{dir_str}\"");
+        assert_eq!(
+            shortcut.resolved_executable(),
+            dir.join("does-not-exist.sh")
+        );
+        assert!(!shortcut.executable_exists());
+    }
+
+    #[test]
+    fn launch_command() {
+        let mut shortcut = Shortcut::new(
+            1,
+            "Game".into(),
+            "\"/games/My Game/game.sh\"".into(),
+            "\"/games/My Game/\"".into(),
+        );
+        let args = |command: &Command| -> Vec<_> {
+            command
+                .get_args()
+                .map(|arg| arg.to_str().unwrap().to_owned())
+                .collect()
+        };
+
+        let command = shortcut.launch_command();
+        assert_eq!(command.get_program(), "/games/My Game/game.sh");
+        assert!(args(&command).is_empty());
+        assert_eq!(
+            command.get_current_dir(),
+            Some(Path::new("/games/My Game/"))
+        );
+
+        shortcut.launch_options = "--windowed -name \"Some Player\" ''".into();
+        let command = shortcut.launch_command();
+        assert_eq!(command.get_program(), "/games/My Game/game.sh");
+        assert_eq!(args(&command), ["--windowed", "-name", "Some Player", ""]);
+
+        shortcut.launch_options =
+            "MANGOHUD=1 DXVK_HUD='fps,memory' gamemoderun -v %command% -x".into();
+        let command = shortcut.launch_command();
+        assert_eq!(command.get_program(), "gamemoderun");
+        assert_eq!(args(&command), ["-v", "/games/My Game/game.sh", "-x"]);
+        let envs: Vec<_> = command.get_envs().collect();
+        assert_eq!(
+            envs,
+            [
+                ("DXVK_HUD".as_ref(), Some("fps,memory".as_ref())),
+                ("MANGOHUD".as_ref(), Some("1".as_ref())),
+            ],
+        );
+
+        // Only environment variables and `%command%`
+        shortcut.launch_options = "PROTON_LOG=1 %command%".into();
+        let command = shortcut.launch_command();
+        assert_eq!(command.get_program(), "/games/My Game/game.sh");
+        assert!(args(&command).is_empty());
+        assert_eq!(command.get_envs().count(), 1);
+
+        // Bare names are left for `$PATH`
+        let shortcut = Shortcut::new(2, "Anki".into(), "\"anki\"".into(), "\"./\"".into());
+        assert_eq!(shortcut.launch_command().get_program(), "anki");
+    }
+
+    #[test]
+    fn empty_and_truncated() {
+        assert_eq!(parse_shortcuts(b""), Some(Vec::new()));
+        assert_eq!(
+            parse_shortcuts(b"\x00shortcuts\x00\x08\x08"),
+            Some(Vec::new())
+        );
+
+        let contents = include_bytes!("../tests/sample_data/shortcuts.vdf");
+        assert_eq!(parse_shortcuts(&contents[..contents.len() / 2]), None);
+    }
+
+    #[test]
+    fn reordered_keys() {
+        let contents =
+            b"\x00shortcuts\x00\x000\x00\x01exe\x00\"anki\"\x00\x00tags\x00\x010\x00Study\x00\x08\
+            \x01StartDir\x00\"./\"\x00\x02IsHidden\x00\x00\x00\x00\x00\x02appid\x00\x05,\x13\xa6\
+            \x01appname\x00Anki\x00\x08\x08\x08";
+        let shortcuts = parse_shortcuts(contents).unwrap();
+        assert_eq!(
+            shortcuts,
+            [Shortcut {
+                app_id: 2786274309,
+                app_name: "Anki".into(),
+                executable: "\"anki\"".into(),
+                start_dir: "\"./\"".into(),
+                tags: vec!["Study".into()],
+                launch_options: String::new(),
             }]
         );
     }
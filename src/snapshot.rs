@@ -0,0 +1,81 @@
+//! Owned, serializable snapshots of a whole Steam installation
+//!
+//! _See [`SteamDir::snapshot()`][crate::SteamDir::snapshot] for taking a snapshot_
+//!
+//! Everything here is plain owned data that implements [`Serialize`] and [`Deserialize`], so
+//! snapshots can be collected on one machine and sent elsewhere with any serde data format
+
+use std::{path::PathBuf, time::SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{App, Library};
+
+/// A snapshot of a Steam installation along with its libraries and (optionally) their apps
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SteamSnapshot {
+    /// The path to the Steam installation directory
+    pub path: PathBuf,
+    /// Every library that's part of the installation
+    pub libraries: Vec<LibrarySnapshot>,
+}
+
+impl SteamSnapshot {
+    pub(crate) fn new(path: PathBuf, libraries: Vec<LibrarySnapshot>) -> Self {
+        Self { path, libraries }
+    }
+}
+
+/// A snapshot of a single [`Library`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct LibrarySnapshot {
+    /// The path to the library's installation directory
+    pub path: PathBuf,
+    /// The IDs of all of the apps contained within the library
+    pub app_ids: Vec<u32>,
+    /// The library's parsed apps, if they were requested when taking the snapshot
+    pub apps: Option<Vec<AppSnapshot>>,
+}
+
+impl LibrarySnapshot {
+    pub(crate) fn new(library: &Library, apps: Option<Vec<AppSnapshot>>) -> Self {
+        Self {
+            path: library.path().to_owned(),
+            app_ids: library.app_ids().to_owned(),
+            apps,
+        }
+    }
+}
+
+/// A snapshot of the most commonly used information from an [`App`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct AppSnapshot {
+    /// _See [`App::app_id`]_
+    pub app_id: u32,
+    /// _See [`App::install_dir`]_
+    pub install_dir: String,
+    /// _See [`App::name`]_
+    pub name: Option<String>,
+    /// _See [`App::build_id`]_
+    pub build_id: Option<u64>,
+    /// _See [`App::size_on_disk`]_
+    pub size_on_disk: Option<u64>,
+    /// _See [`App::last_updated`]_
+    pub last_updated: Option<SystemTime>,
+}
+
+impl From<&App> for AppSnapshot {
+    fn from(app: &App) -> Self {
+        Self {
+            app_id: app.app_id,
+            install_dir: app.install_dir.clone(),
+            name: app.name.clone(),
+            build_id: app.build_id,
+            size_on_disk: app.size_on_disk,
+            last_updated: app.last_updated,
+        }
+    }
+}
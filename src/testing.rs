@@ -0,0 +1,430 @@
+//! Fake Steam installations for testing code that uses `steamlocate`
+//!
+//! _Requires the `testing` feature_
+//!
+//! [`FakeSteamDir`] lays out a realistic Steam installation (app manifests, libraries, and
+//! shortcuts) in a temporary directory, so that your tests don't need a real Steam install. The
+//! generated files are deterministic: the same builder always produces the same contents
+//!
+//! # Example
+//!
+//! ```
+//! use steamlocate::testing::{FakeApp, FakeLibrary, FakeShortcut, FakeSteamDir};
+//!
+//! let fake = FakeSteamDir::builder()
+//!     .app(FakeApp::new(4_000, "Garry's Mod").install_dir("GarrysMod"))
+//!     .library(FakeLibrary::new().app(FakeApp::new(599_140, "Graveyard Keeper")))
+//!     .shortcut(FakeShortcut::new("Moonlighter", "/games/moonlighter/start.sh"))
+//!     .build()?;
+//! let steam_dir = fake.steam_dir();
+//!
+//! let (gmod, _library) = steam_dir.find_app(4_000)?.unwrap();
+//! assert_eq!(gmod.install_dir, "GarrysMod");
+//! assert_eq!(steam_dir.library_count()?, 2);
+//! let moonlighter = steam_dir.shortcuts()?.next().unwrap()?;
+//! assert_eq!(moonlighter.app_name, "Moonlighter");
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{io, path::Path};
+
+use crate::{
+    __private_tests::helpers::{AppFile, TempLibrary, TempSteamDir, TempSteamDirBuilder},
+    SteamDir,
+};
+
+/// A fake Steam installation
+///
+/// Everything is written to a temporary directory that's removed when this is dropped. Use
+/// [`FakeSteamDirBuilder::build_in()`] instead if you want to keep the files around
+#[derive(Debug)]
+pub struct FakeSteamDir(TempSteamDir);
+
+impl FakeSteamDir {
+    /// Starts building an installation with no apps, extra libraries, or shortcuts
+    pub fn builder() -> FakeSteamDirBuilder {
+        FakeSteamDirBuilder::default()
+    }
+
+    /// The [`SteamDir`] for the fake installation
+    pub fn steam_dir(&self) -> &SteamDir {
+        self.0.steam_dir()
+    }
+}
+
+/// Builds a [`FakeSteamDir`]
+///
+/// _See [`FakeSteamDir::builder()`]_
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct FakeSteamDirBuilder {
+    apps: Vec<FakeApp>,
+    libraries: Vec<FakeLibrary>,
+    shortcuts: Vec<FakeShortcut>,
+    config: Option<String>,
+    user_id: Option<u32>,
+}
+
+impl FakeSteamDirBuilder {
+    /// Installs `app` in the library inside of the Steam installation itself
+    pub fn app(mut self, app: FakeApp) -> Self {
+        self.apps.push(app);
+        self
+    }
+
+    /// Adds an additional library
+    pub fn library(mut self, library: FakeLibrary) -> Self {
+        self.libraries.push(library);
+        self
+    }
+
+    /// Adds a non-Steam game to the user's `shortcuts.vdf`
+    pub fn shortcut(mut self, shortcut: FakeShortcut) -> Self {
+        self.shortcuts.push(shortcut);
+        self
+    }
+
+    /// Sets the raw contents of `config/config.vdf`
+    pub fn config(mut self, contents: impl Into<String>) -> Self {
+        self.config = Some(contents.into());
+        self
+    }
+
+    /// Sets the account ID used for the user's `userdata/<USER_ID>` directory
+    ///
+    /// Defaults to `123123123`
+    pub fn user_id(mut self, user_id: u32) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    /// Builds the installation in a new temporary directory
+    pub fn build(self) -> io::Result<FakeSteamDir> {
+        self.into_temp().finish().map(FakeSteamDir)
+    }
+
+    /// Builds the installation in `dir`
+    ///
+    /// The Steam installation is created at `dir/Steam` and each additional library at
+    /// `dir/library-<N>`. Nothing is cleaned up afterwards
+    pub fn build_in(self, dir: &Path) -> io::Result<FakeSteamDir> {
+        self.into_temp().finish_in(dir).map(FakeSteamDir)
+    }
+
+    fn into_temp(self) -> TempSteamDirBuilder {
+        let Self {
+            apps,
+            libraries,
+            shortcuts,
+            config,
+            user_id,
+        } = self;
+
+        let mut builder = TempSteamDir::builder();
+        for app in &apps {
+            builder = builder.app(app.to_app_file());
+        }
+        for library in &libraries {
+            let mut temp_library = TempLibrary::default();
+            for app in &library.apps {
+                temp_library = temp_library.app(app.to_app_file());
+            }
+            builder = builder.library(temp_library);
+        }
+        if !shortcuts.is_empty() {
+            builder = builder.shortcuts_contents(shortcuts_vdf(&shortcuts));
+        }
+        if let Some(config) = config {
+            builder = builder.config_contents(config);
+        }
+        if let Some(user_id) = user_id {
+            builder = builder.user_id(user_id);
+        }
+        builder
+    }
+}
+
+/// An additional library for a [`FakeSteamDir`]
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct FakeLibrary {
+    apps: Vec<FakeApp>,
+}
+
+impl FakeLibrary {
+    /// An empty library
+    ///
+    /// Its path is picked when the [`FakeSteamDir`] gets built
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Installs `app` in this library
+    pub fn app(mut self, app: FakeApp) -> Self {
+        self.apps.push(app);
+        self
+    }
+}
+
+/// An installed app for a [`FakeSteamDir`]
+///
+/// A minimal `appmanifest_<APP_ID>.acf` is generated from the configured values unless the raw
+/// contents are provided with [`FakeApp::manifest_contents()`]
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct FakeApp {
+    app_id: u32,
+    name: String,
+    install_dir: Option<String>,
+    build_id: u64,
+    size_on_disk: u64,
+    contents: Option<String>,
+}
+
+impl FakeApp {
+    /// An app with the given ID and name that's installed to a folder named after the app
+    pub fn new(app_id: u32, name: impl Into<String>) -> Self {
+        Self {
+            app_id,
+            name: name.into(),
+            install_dir: None,
+            build_id: 0,
+            size_on_disk: 0,
+            contents: None,
+        }
+    }
+
+    /// Sets the folder within `steamapps/common` that the app is installed to
+    pub fn install_dir(mut self, install_dir: impl Into<String>) -> Self {
+        self.install_dir = Some(install_dir.into());
+        self
+    }
+
+    /// Sets the manifest's `buildid`
+    ///
+    /// Defaults to `0`
+    pub fn build_id(mut self, build_id: u64) -> Self {
+        self.build_id = build_id;
+        self
+    }
+
+    /// Sets the app's size in bytes, both in its manifest's `SizeOnDisk` and in
+    /// `libraryfolders.vdf`
+    ///
+    /// Defaults to `0`. No files of that size are actually written
+    pub fn size_on_disk(mut self, size_on_disk: u64) -> Self {
+        self.size_on_disk = size_on_disk;
+        self
+    }
+
+    /// Uses `contents` as the app's manifest verbatim
+    ///
+    /// The app's install directory is still created from [`FakeApp::install_dir()`], so keep the
+    /// two in sync
+    pub fn manifest_contents(mut self, contents: impl Into<String>) -> Self {
+        self.contents = Some(contents.into());
+        self
+    }
+
+    fn install_dir_name(&self) -> &str {
+        self.install_dir.as_deref().unwrap_or(&self.name)
+    }
+
+    fn to_app_file(&self) -> AppFile {
+        AppFile::new(self.app_id, self.install_dir_name(), self.manifest())
+            .size_on_disk(self.size_on_disk)
+    }
+
+    fn manifest(&self) -> String {
+        if let Some(contents) = &self.contents {
+            return contents.clone();
+        }
+
+        format!(
+            "\"AppState\"\n{{\n\t\"appid\"\t\t\"{}\"\n\t\"name\"\t\t\"{}\"\n\t\"StateFlags\"\t\t\"4\"\n\t\"installdir\"\t\t\"{}\"\n\t\"SizeOnDisk\"\t\t\"{}\"\n\t\"buildid\"\t\t\"{}\"\n}}\n",
+            self.app_id,
+            escape(&self.name),
+            escape(self.install_dir_name()),
+            self.size_on_disk,
+            self.build_id,
+        )
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// A non-Steam game for a [`FakeSteamDir`]
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct FakeShortcut {
+    app_name: String,
+    executable: String,
+    start_dir: Option<String>,
+    app_id: Option<u32>,
+    launch_options: String,
+}
+
+impl FakeShortcut {
+    /// A shortcut that launches `executable`
+    ///
+    /// The executable is quoted like Steam does when adding a shortcut
+    pub fn new(app_name: impl Into<String>, executable: impl AsRef<str>) -> Self {
+        Self {
+            app_name: app_name.into(),
+            executable: format!("\"{}\"", executable.as_ref()),
+            start_dir: None,
+            app_id: None,
+            launch_options: String::new(),
+        }
+    }
+
+    /// Sets the directory that the executable is started in
+    ///
+    /// Defaults to the executable's parent directory
+    pub fn start_dir(mut self, start_dir: impl AsRef<str>) -> Self {
+        self.start_dir = Some(format!("\"{}\"", start_dir.as_ref()));
+        self
+    }
+
+    /// Sets the app ID that Steam assigned to the shortcut
+    ///
+    /// Defaults to the same ID that Steam would generate
+    pub fn app_id(mut self, app_id: u32) -> Self {
+        self.app_id = Some(app_id);
+        self
+    }
+
+    /// Sets the extra arguments that the shortcut is launched with
+    pub fn launch_options(mut self, launch_options: impl Into<String>) -> Self {
+        self.launch_options = launch_options.into();
+        self
+    }
+
+    fn start_dir_or_default(&self) -> String {
+        self.start_dir.clone().unwrap_or_else(|| {
+            let executable = self.executable.trim_matches('"');
+            let parent = Path::new(executable)
+                .parent()
+                .map(|parent| parent.to_string_lossy())
+                .unwrap_or_default();
+            format!("\"{parent}\"")
+        })
+    }
+}
+
+/// Serializes shortcuts to Steam's binary VDF format
+fn shortcuts_vdf(shortcuts: &[FakeShortcut]) -> Vec<u8> {
+    fn key(out: &mut Vec<u8>, ty: u8, key: &str) {
+        out.push(ty);
+        out.extend_from_slice(key.as_bytes());
+        out.push(0);
+    }
+
+    fn string(out: &mut Vec<u8>, name: &str, value: &str) {
+        key(out, 0x01, name);
+        out.extend_from_slice(value.as_bytes());
+        out.push(0);
+    }
+
+    let mut out = Vec::new();
+    key(&mut out, 0x00, "shortcuts");
+    for (i, shortcut) in shortcuts.iter().enumerate() {
+        key(&mut out, 0x00, &i.to_string());
+        let app_id = shortcut.app_id.unwrap_or_else(|| {
+            let steam_id =
+                crate::shortcut::calculate_steam_id(&shortcut.executable, &shortcut.app_name);
+            (steam_id >> 32) as u32
+        });
+        key(&mut out, 0x02, "appid");
+        out.extend_from_slice(&app_id.to_le_bytes());
+        string(&mut out, "AppName", &shortcut.app_name);
+        string(&mut out, "Exe", &shortcut.executable);
+        string(&mut out, "StartDir", &shortcut.start_dir_or_default());
+        string(&mut out, "LaunchOptions", &shortcut.launch_options);
+        out.push(0x08);
+    }
+    out.extend_from_slice(&[0x08, 0x08]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use crate::__private_tests::TestResult;
+
+    #[test]
+    fn deterministic() -> TestResult {
+        let builder = FakeSteamDir::builder()
+            .app(FakeApp::new(4_000, "Garry's Mod").install_dir("GarrysMod"))
+            .library(FakeLibrary::new().app(FakeApp::new(599_140, "Graveyard Keeper")))
+            .shortcut(FakeShortcut::new(
+                "Moonlighter",
+                "/games/moonlighter/start.sh",
+            ));
+        let a = builder.clone().build()?;
+        let b = builder.build()?;
+
+        let read = |fake: &FakeSteamDir, path: &str| {
+            fs::read_to_string(fake.steam_dir().path().join(path))
+                .unwrap()
+                .replace(
+                    &*fake.steam_dir().path().parent().unwrap().to_string_lossy(),
+                    "",
+                )
+        };
+        for path in [
+            "steamapps/appmanifest_4000.acf",
+            "steamapps/libraryfolders.vdf",
+        ] {
+            assert_eq!(read(&a, path), read(&b, path));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> TestResult {
+        let fake = FakeSteamDir::builder()
+            .app(
+                FakeApp::new(4_000, "Garry's Mod")
+                    .install_dir("GarrysMod")
+                    .build_id(1_234)
+                    .size_on_disk(5_678),
+            )
+            .library(FakeLibrary::new().app(FakeApp::new(599_140, "Graveyard \"Keeper\"")))
+            .shortcut(FakeShortcut::new(
+                "Moonlighter",
+                "/games/moonlighter/start.sh",
+            ))
+            .shortcut(FakeShortcut::new("Other", "/other").app_id(42))
+            .build()?;
+        let steam_dir = fake.steam_dir();
+
+        let (gmod, library) = steam_dir.find_app(4_000)?.unwrap();
+        assert_eq!(gmod.name.as_deref(), Some("Garry's Mod"));
+        assert_eq!(gmod.build_id, Some(1_234));
+        assert_eq!(gmod.size_on_disk, Some(5_678));
+        assert!(library.resolve_app_dir(&gmod).is_dir());
+
+        let (keeper, _) = steam_dir.find_app(599_140)?.unwrap();
+        assert_eq!(keeper.name.as_deref(), Some("Graveyard \"Keeper\""));
+        assert_eq!(steam_dir.app_ids()?, [4_000, 599_140]);
+
+        let shortcuts: Vec<_> = steam_dir.shortcuts()?.collect::<crate::Result<_>>()?;
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].app_name, "Moonlighter");
+        assert_eq!(shortcuts[0].start_dir, "\"/games/moonlighter\"");
+        assert_eq!(
+            shortcuts[0].steam_id() >> 32,
+            u64::from(shortcuts[0].app_id)
+        );
+        assert_eq!(shortcuts[1].app_id, 42);
+
+        Ok(())
+    }
+}
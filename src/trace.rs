@@ -0,0 +1,22 @@
+//! Thin wrappers over `tracing`'s macros
+//!
+//! With the `tracing` feature disabled these expand to nothing at all, so instrumenting the crate
+//! is free for everyone that doesn't opt in. Only log values that are used elsewhere too, so that
+//! disabling the feature doesn't leave behind unused variables
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)*);
+    };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($arg)*);
+    };
+}
+
+pub(crate) use debug;
+pub(crate) use trace;
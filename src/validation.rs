@@ -0,0 +1,90 @@
+//! A health check of a Steam installation
+//!
+//! _See [`SteamDir::validate()`][crate::SteamDir::validate] for running the checks_
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::Error;
+
+/// The outcome of every check performed by [`SteamDir::validate()`][crate::SteamDir::validate]
+///
+/// [`Display`][fmt::Display]ing the report gives a line per check which is suitable for showing
+/// to a user directly
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ValidationReport {
+    /// Whether the `steamapps` directory is readable
+    pub steamapps_dir: Check,
+    /// Whether `steamapps/libraryfolders.vdf` could be read and parsed
+    pub library_folders: Check,
+    /// Whether each of the libraries listed in `libraryfolders.vdf` could be read
+    ///
+    /// This is empty when [`library_folders`][Self::library_folders] fails
+    pub libraries: Vec<Check>,
+    /// Whether `config/config.vdf` (or `steam/config/config.vdf`) could be read and parsed
+    pub config: Check,
+    /// Whether the `userdata` directory is readable
+    pub userdata_dir: Check,
+}
+
+impl ValidationReport {
+    /// Returns an [`Iterator`] over every check along with a short description of it
+    pub fn checks(&self) -> impl Iterator<Item = (&'static str, &Check)> {
+        [
+            ("steamapps directory", &self.steamapps_dir),
+            ("libraryfolders.vdf", &self.library_folders),
+        ]
+        .into_iter()
+        .chain(self.libraries.iter().map(|check| ("library", check)))
+        .chain([
+            ("config.vdf", &self.config),
+            ("userdata directory", &self.userdata_dir),
+        ])
+    }
+
+    /// Whether every check passed
+    pub fn is_healthy(&self) -> bool {
+        self.checks().all(|(_, check)| check.passed())
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (description, check) in self.checks() {
+            let path = check.path.display();
+            match &check.error {
+                None => writeln!(f, "[ok]   {description}: {path}")?,
+                Some(err) => writeln!(f, "[fail] {description}: {path} - {err}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A single check from a [`ValidationReport`]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Check {
+    /// The path that was checked
+    pub path: PathBuf,
+    /// The error encountered while checking, if any
+    pub error: Option<Error>,
+}
+
+impl Check {
+    pub(crate) fn new<T>(path: &Path, result: crate::Result<T>) -> Self {
+        Self {
+            path: path.to_owned(),
+            error: result.err(),
+        }
+    }
+
+    /// Whether the check passed
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
@@ -0,0 +1,74 @@
+//! Parsing other VDF files in the Steam installation
+//!
+//! `steamlocate` only models the files that it needs, but Steam keeps plenty of others around
+//! (controller configs, tool manifests, etc.). [`parse_file()`] deserializes any of them into your
+//! own types with the same conventions and error handling used throughout the rest of the crate
+
+use std::{fs, path::Path};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{ParseError, ParseErrorKind},
+    Error, Result,
+};
+
+/// Reads and deserializes the text VDF file at `path` into `T`
+///
+/// The file's root key is skipped, so `T` should model the root's value. Failing to read the file
+/// returns an [`Error::Io`] while failing to parse or deserialize it returns an [`Error::Parse`]
+/// with [`ParseErrorKind::Custom`] whose [`ParseError::label()`][crate::error::ParseError::label]
+/// is `kind_label`. The label can be anything that helps to identify the file, like its name
+///
+/// # Example
+///
+/// ```
+/// # use steamlocate::__private_tests::prelude::*;
+/// # let temp_steam_dir: TempSteamDir = SampleConfig::Linux.try_into()?;
+/// # let steam_dir = temp_steam_dir.steam_dir();
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// #[serde(rename_all = "PascalCase")]
+/// struct Store {
+///     software: Software,
+/// }
+///
+/// #[derive(Deserialize)]
+/// #[serde(rename_all = "PascalCase")]
+/// struct Software {
+///     valve: Valve,
+/// }
+///
+/// #[derive(Deserialize)]
+/// #[serde(rename_all = "PascalCase")]
+/// struct Valve {
+///     steam: Steam,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Steam {
+///     #[serde(rename = "AutoUpdateWindowEnabled")]
+///     auto_update_window_enabled: Option<bool>,
+/// }
+///
+/// # /*
+/// let steam_dir = SteamDir::locate()?;
+/// # */
+/// let config_path = steam_dir.config_dir().join("config.vdf");
+/// let store: Store = steamlocate::vdf::parse_file(&config_path, "config")?;
+/// println!("{:?}", store.software.valve.steam.auto_update_window_enabled);
+/// # Ok::<_, TestError>(())
+/// ```
+pub fn parse_file<T: DeserializeOwned>(path: &Path, kind_label: &str) -> Result<T> {
+    let contents = fs::read_to_string(path).map_err(|io| Error::io(io, path))?;
+    // Files saved by some Windows text editors start with a byte order mark
+    let contents = contents.strip_prefix('\u{feff}').unwrap_or(&contents);
+    keyvalues_serde::from_str(contents).map_err(|de| {
+        Error::parse(
+            ParseErrorKind::Custom,
+            ParseError::from_serde(de).with_label(kind_label),
+            path,
+        )
+    })
+}